@@ -0,0 +1,25 @@
+use dioxus::prelude::*;
+use kumou_japanese::Paradigm;
+
+#[component]
+pub fn ConjugationTable(paradigm: Paradigm) -> Element {
+    rsx! {
+        div { class: "conjugation-panel",
+            h3 { class: "detail-heading",
+                "Conjugation of {paradigm.lemma} "
+                span { class: "conj-class", "({paradigm.class.label()})" }
+            }
+            table { class: "token-table conjugation-table",
+                tbody {
+                    for form in &paradigm.forms {
+                        tr {
+                            td { class: "conj-form-name", "{form.form.label()}" }
+                            td { class: "conj-form-name-ja", "{form.form.label_ja()}" }
+                            td { class: "surface-cell", "{form.text}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use dioxus::prelude::*;
+use kumou_japanese::DependencyTree;
+
+const CHAR_WIDTH: f64 = 18.0;
+const NODE_PADDING: f64 = 24.0;
+const ARC_BASE: f64 = 24.0;
+const ARC_STEP: f64 = 22.0;
+
+/// Arc diagram of bunsetsu dependencies, arcs drawn from modifier to head
+#[component]
+pub fn DependencyArcs(tree: DependencyTree) -> Element {
+    let widths: Vec<f64> = tree
+        .nodes
+        .iter()
+        .map(|n| n.bunsetsu.surface.chars().count() as f64 * CHAR_WIDTH + NODE_PADDING)
+        .collect();
+    let centers: Vec<f64> = widths
+        .iter()
+        .scan(0.0, |left, width| {
+            let center = *left + width / 2.0;
+            *left += width;
+            Some(center)
+        })
+        .collect();
+    let total_width: f64 = widths.iter().sum();
+
+    let arc_height = |from: usize, to: usize| ARC_BASE + ARC_STEP * (to.abs_diff(from) - 1) as f64;
+    let max_height = tree
+        .nodes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, n)| n.head.map(|h| arc_height(i, h)))
+        .fold(0.0, f64::max);
+    let baseline = max_height + 16.0;
+    let height = baseline + 28.0;
+
+    rsx! {
+        div { class: "dependency-panel",
+            h3 { class: "detail-heading", "Dependencies" }
+            div { class: "dependency-scroll",
+                svg {
+                    class: "dependency-arcs",
+                    width: "{total_width}",
+                    height: "{height}",
+                    view_box: "0 0 {total_width} {height}",
+                    for (i, node) in tree.nodes.iter().enumerate() {
+                        if let Some(head) = node.head {
+                            {
+                                let (x1, x2) = (centers[i], centers[head]);
+                                let top = baseline - arc_height(i, head) * 4.0 / 3.0;
+                                let label_y = baseline - arc_height(i, head) - 4.0;
+                                rsx! {
+                                    path {
+                                        class: "dependency-arc",
+                                        d: "M {x1} {baseline} C {x1} {top}, {x2} {top}, {x2} {baseline}",
+                                    }
+                                    circle { class: "dependency-head", cx: "{x2}", cy: "{baseline}", r: "3" }
+                                    text {
+                                        class: "dependency-label",
+                                        x: "{(x1 + x2) / 2.0}",
+                                        y: "{label_y}",
+                                        text_anchor: "middle",
+                                        "{node.relation.label()}"
+                                    }
+                                }
+                            }
+                        }
+                        text {
+                            class: if node.head.is_none() { "dependency-node dependency-root" } else { "dependency-node" },
+                            x: "{centers[i]}",
+                            y: "{baseline + 22.0}",
+                            text_anchor: "middle",
+                            "{node.bunsetsu.surface}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::server_fns::analyze_sentence;
+use dioxus::prelude::*;
+use kumou_japanese::{AnalyzedSentence, SegmentationMode, original_furigana};
+
+/// Renders `text` with furigana over its kanji as `<ruby>` elements.
+/// Tokens are shown as written in `text`, not as normalized for analysis.
+/// Falls back to the plain text while loading or if analysis fails.
+/// Tokens inside `highlight`, a char range of `text`, are highlighted.
+/// A `prefetched` analysis of `text` is used instead of asking the server.
+#[component]
+pub fn FuriganaText(
+    text: String,
+    #[props(default)] highlight: Option<(usize, usize)>,
+    #[props(default)] prefetched: Option<AnalyzedSentence>,
+) -> Element {
+    let plain = text.clone();
+    let analysis = use_server_future(move || {
+        let text = text.clone();
+        let prefetched = prefetched.clone();
+        async move {
+            match prefetched {
+                Some(sentence) => Ok(sentence),
+                None => analyze_sentence(text, SegmentationMode::Normal).await,
+            }
+        }
+    })?;
+
+    match &*analysis.read() {
+        Some(Ok(result)) => rsx! {
+            for token in &result.tokens {
+                span {
+                    class: if highlight.is_some_and(|(start, end)| {
+                        start <= token.char_start && token.char_end <= end
+                    }) {
+                        "token-highlight"
+                    },
+                    for segment in original_furigana(result, token) {
+                        if let Some(reading) = segment.reading {
+                            ruby { class: "furigana",
+                                "{segment.text}"
+                                rt { "{reading}" }
+                            }
+                        } else {
+                            "{segment.text}"
+                        }
+                    }
+                }
+            }
+        },
+        _ => rsx! { "{plain}" },
+    }
+}
@@ -0,0 +1,57 @@
+use crate::server_fns::get_kanji;
+use dioxus::prelude::*;
+
+/// KANJIDIC2 readings, meanings, stroke count and grade of one kanji
+#[component]
+pub fn KanjiPanel(literal: char, on_close: EventHandler<()>) -> Element {
+    let info = use_server_future(use_reactive!(|literal| get_kanji(literal)))?;
+
+    rsx! {
+        div { class: "kanji-panel",
+            button { class: "kanji-close", onclick: move |_| on_close.call(()), "×" }
+            match &*info.read() {
+                Some(Ok(info)) => rsx! {
+                    div { class: "kanji-literal", "{info.literal}" }
+                    div { class: "kanji-details",
+                        if !info.meanings.is_empty() {
+                            div { class: "kanji-meanings", "{info.meanings.join(\", \")}" }
+                        }
+                        if !info.on_readings.is_empty() {
+                            div { class: "kanji-row",
+                                span { class: "kanji-row-label", "On" }
+                                "{info.on_readings.join(\"、\")}"
+                            }
+                        }
+                        if !info.kun_readings.is_empty() {
+                            div { class: "kanji-row",
+                                span { class: "kanji-row-label", "Kun" }
+                                "{info.kun_readings.join(\"、\")}"
+                            }
+                        }
+                        if !info.nanori.is_empty() {
+                            div { class: "kanji-row",
+                                span { class: "kanji-row-label", "Names" }
+                                "{info.nanori.join(\"、\")}"
+                            }
+                        }
+                        div { class: "kanji-row",
+                            span { class: "kanji-row-label", "Strokes" }
+                            "{info.stroke_count}"
+                            if let Some(grade) = info.grade_label() {
+                                span { class: "kanji-badge", "{grade}" }
+                            }
+                            if let Some(frequency) = info.frequency {
+                                span { class: "kanji-badge", "Frequency #{frequency}" }
+                            }
+                            if let Some(level) = info.jlpt {
+                                span { class: "kanji-badge", title: "Former four-level JLPT", "Old JLPT {level}" }
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! { p { class: "error", "Kanji lookup error: {e}" } },
+                None => rsx! { p { class: "loading", "Looking up {literal}..." } },
+            }
+        }
+    }
+}
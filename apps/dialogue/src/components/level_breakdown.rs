@@ -0,0 +1,40 @@
+use crate::server_fns::get_dialogue_levels;
+use dioxus::prelude::*;
+use kumou_japanese::{JlptLevel, LevelCounts};
+
+/// JLPT level counts of a dialogue's words and kanji
+///
+/// Nothing is shown when the server has no JLPT lists configured.
+#[component]
+pub fn LevelBreakdown(dialogue_id: u32) -> Element {
+    let levels = use_server_future(move || get_dialogue_levels(dialogue_id))?;
+
+    match &*levels.read() {
+        Some(Ok(breakdown)) => rsx! {
+            div { class: "level-breakdown",
+                LevelRow { label: "Vocabulary", counts: breakdown.vocabulary.clone() }
+                LevelRow { label: "Kanji", counts: breakdown.kanji.clone() }
+            }
+        },
+        _ => rsx! {},
+    }
+}
+
+#[component]
+fn LevelRow(label: String, counts: LevelCounts) -> Element {
+    rsx! {
+        div { class: "level-row",
+            span { class: "level-row-label", "{label}" }
+            for level in JlptLevel::ALL {
+                span { class: "level-count {level.css_class()}",
+                    "{level.as_str()} "
+                    strong { "{counts.get(level)}" }
+                }
+            }
+            span { class: "level-count level-unlisted",
+                "Other "
+                strong { "{counts.unlisted}" }
+            }
+        }
+    }
+}
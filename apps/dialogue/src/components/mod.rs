@@ -1,4 +1,18 @@
+mod conjugation_table;
+mod dependency_arcs;
+mod furigana_text;
+mod kanji_panel;
+mod level_breakdown;
+mod pitch_contour;
 mod sentence_analysis;
 mod speak_button;
+mod style_version;
+pub use conjugation_table::ConjugationTable;
+pub use dependency_arcs::DependencyArcs;
+pub use furigana_text::FuriganaText;
+pub use kanji_panel::KanjiPanel;
+pub use level_breakdown::LevelBreakdown;
+pub use pitch_contour::PitchContour;
 pub use sentence_analysis::SentenceAnalysis;
 pub use speak_button::SpeakButton;
+pub use style_version::StyleVersion;
@@ -0,0 +1,41 @@
+use dioxus::prelude::*;
+use kumou_japanese::{PitchAccent, ReadingDisplay, katakana_to_hiragana, split_morae};
+
+/// Reading with a line over high morae and a step where pitch rises or falls
+///
+/// The trailing empty mark shows the pitch of a following particle, which
+/// tells heiban and odaka words apart.
+#[component]
+pub fn PitchContour(reading: String, accent: PitchAccent, display: ReadingDisplay) -> Element {
+    let reading = if display == ReadingDisplay::Hiragana {
+        katakana_to_hiragana(&reading)
+    } else {
+        reading
+    };
+    let morae = split_morae(&reading);
+    let pitches = accent.pitches();
+    let pattern = accent.pattern();
+
+    let class_of = move |i: usize| {
+        let high = pitches[i];
+        let mut class = String::from(if high { "mora mora-high" } else { "mora mora-low" });
+        if i > 0 && high && !pitches[i - 1] {
+            class.push_str(" mora-rise");
+        }
+        if accent.nucleus > 0 && i + 1 == accent.nucleus {
+            class.push_str(" mora-fall");
+        }
+        class
+    };
+
+    rsx! {
+        div {
+            class: "pitch-contour",
+            title: "{pattern.label()} ({pattern.label_ja()}) [{accent.nucleus}]",
+            for (i, mora) in morae.into_iter().enumerate() {
+                span { class: class_of(i), "{mora}" }
+            }
+            span { class: "{class_of(accent.morae)} mora-particle" }
+        }
+    }
+}
@@ -1,93 +1,398 @@
-use crate::components::SpeakButton;
+use crate::components::{
+    ConjugationTable, DependencyArcs, KanjiPanel, PitchContour, SpeakButton,
+};
 use crate::server_fns::analyze_sentence;
 use dioxus::prelude::*;
-use kumou_japanese::{pos_css_class, pos_english};
+use kumou_japanese::{
+    AnalyzedSentence, AnalyzedToken, LabelCatalog, LabelLanguage, LabelText, ReadingDisplay,
+    SegmentationMode, chunk_bunsetsu, detect_grammar, display_reading, find_numerals,
+    inflection_chains, is_kanji, pos_css_class, pos_english, token_paradigm,
+};
 
 #[component]
-pub fn SentenceAnalysis(text: String) -> Element {
+pub fn SentenceAnalysis(
+    text: String,
+    #[props(default)] reading_display: ReadingDisplay,
+    /// Language of the POS and conjugation labels in the token table
+    #[props(default)]
+    label_language: LabelLanguage,
+    /// Called with the char range in `text` of the token under the pointer
+    #[props(default)]
+    on_token_hover: EventHandler<Option<(usize, usize)>>,
+    /// Analysis of `text` in `prefetched_mode`, used instead of asking the
+    /// server while that mode is selected
+    #[props(default)]
+    prefetched: Option<AnalyzedSentence>,
+    #[props(default)]
+    prefetched_mode: SegmentationMode,
+) -> Element {
+    let mut mode = use_signal(SegmentationMode::default);
+    let mut selected_token = use_signal(|| Option::<usize>::None);
+    let mut show_phrases = use_signal(|| false);
+    let mut selected_kanji = use_signal(|| Option::<char>::None);
+    let mut selected_pattern = use_signal(|| Option::<usize>::None);
+
     let analysis = use_server_future(move || {
         let text = text.clone();
-        async move { analyze_sentence(text).await }
+        let mode = mode();
+        let prefetched = prefetched.clone();
+        async move {
+            match prefetched {
+                Some(sentence) if mode == prefetched_mode => Ok(sentence),
+                _ => analyze_sentence(text, mode).await,
+            }
+        }
     })?;
 
     rsx! {
         div { class: "analysis-panel",
             h2 { class: "analysis-title", "Sentence Analysis" }
 
+            div { class: "mode-toggle",
+                button {
+                    class: if mode() == SegmentationMode::Normal { "mode-btn mode-btn-active" } else { "mode-btn" },
+                    title: "Keep compounds such as 東京国際空港 whole",
+                    onclick: move |_| {
+                        mode.set(SegmentationMode::Normal);
+                        selected_token.set(None);
+                        selected_pattern.set(None);
+                    },
+                    "Whole compounds"
+                }
+                button {
+                    class: if mode() == SegmentationMode::Decompose { "mode-btn mode-btn-active" } else { "mode-btn" },
+                    title: "Split compounds such as 東京国際空港 into parts",
+                    onclick: move |_| {
+                        mode.set(SegmentationMode::Decompose);
+                        selected_token.set(None);
+                        selected_pattern.set(None);
+                    },
+                    "Decomposed"
+                }
+            }
+
             match &*analysis.read() {
-                Some(Ok(result)) => rsx! {
-                    div { class: "analysis-original",
-                        span { class: "label", "Original: " }
-                        "{result.text}"
-                        SpeakButton { text: result.text.clone() }
-                    }
+                Some(Ok(result)) => {
+                    let patterns = detect_grammar(result);
+                    let highlighted = selected_pattern()
+                        .and_then(|index| patterns.get(index))
+                        .map(|m| m.start..m.end);
+                    rsx! {
+                        div { class: "analysis-original",
+                            span { class: "label", "Original: " }
+                            "{result.text}"
+                            SpeakButton { text: result.text.clone() }
+                        }
 
-                    div { class: "token-flow",
-                        for token in &result.tokens {
-                            div {
-                                class: "token-chip {pos_css_class(&token.pos.major)}",
-                                div { class: "token-surface", "{token.surface}" }
-                                div { class: "token-reading", "{token.reading}" }
-                                div { class: "token-pos", "{pos_english(&token.pos.major)}" }
-                                SpeakButton { text: token.surface.clone(), small: true }
+                        div { class: "mode-toggle",
+                            button {
+                                class: if !show_phrases() { "mode-btn mode-btn-active" } else { "mode-btn" },
+                                onclick: move |_| show_phrases.set(false),
+                                "Tokens"
+                            }
+                            button {
+                                class: if show_phrases() { "mode-btn mode-btn-active" } else { "mode-btn" },
+                                onclick: move |_| show_phrases.set(true),
+                                "Phrases"
                             }
                         }
-                    }
 
-                    h3 { class: "detail-heading", "Token Details" }
-                    div { class: "token-table-wrapper",
-                        table { class: "token-table",
-                            thead {
-                                tr {
-                                    th { "Surface" }
-                                    th { "Reading" }
-                                    th { "Base Form" }
-                                    th { "POS" }
-                                    th { "POS Detail" }
-                                    th { "Conjugation" }
-                                    th { "" }
+                        if show_phrases() {
+                            div { class: "phrase-flow",
+                                for bunsetsu in chunk_bunsetsu(result) {
+                                    div { class: "phrase-chip",
+                                        for (index, token) in bunsetsu.tokens(result).iter().enumerate() {
+                                            span {
+                                                class: if bunsetsu.start + index == bunsetsu.head {
+                                                    "phrase-token phrase-head {pos_css_class(&token.pos.major)}"
+                                                } else {
+                                                    "phrase-token {pos_css_class(&token.pos.major)}"
+                                                },
+                                                "{token.surface}"
+                                            }
+                                        }
+                                    }
                                 }
                             }
-                            tbody {
-                                for token in &result.tokens {
-                                    tr { class: pos_css_class(&token.pos.major),
-                                        td { class: "surface-cell", "{token.surface}" }
-                                        td { "{token.reading}" }
-                                        td { "{token.base_form}" }
-                                        td {
-                                            span { class: "pos-badge {pos_css_class(&token.pos.major)}",
-                                                "{pos_english(&token.pos.major)}"
-                                            }
+                        }
+
+                        div { class: "token-flow",
+                            for (index, token) in result.tokens.iter().enumerate() {
+                                div {
+                                    class: token_chip_class(
+                                        token,
+                                        selected_token() == Some(index),
+                                        highlighted.as_ref().is_some_and(|span| span.contains(&index)),
+                                    ),
+                                    onclick: move |_| {
+                                        if selected_token() == Some(index) {
+                                            selected_token.set(None);
+                                        } else {
+                                            selected_token.set(Some(index));
                                         }
-                                        td { class: "pos-detail",
-                                            "{token.pos.major}"
-                                            if token.pos.sub1 != "*" {
-                                                " / {token.pos.sub1}"
+                                    },
+                                    onmouseenter: {
+                                        let span = (token.char_start, token.char_end);
+                                        move |_| on_token_hover.call(Some(span))
+                                    },
+                                    onmouseleave: move |_| on_token_hover.call(None),
+                                    div { class: "token-surface",
+                                        for c in token.surface.chars() {
+                                            if is_kanji(c) {
+                                                span {
+                                                    class: "kanji-link",
+                                                    title: "Show kanji details",
+                                                    onclick: move |e| {
+                                                        e.stop_propagation();
+                                                        selected_kanji.set(Some(c));
+                                                    },
+                                                    "{c}"
+                                                }
+                                            } else {
+                                                "{c}"
                                             }
-                                            if token.pos.sub2 != "*" {
-                                                " / {token.pos.sub2}"
+                                        }
+                                    }
+                                    if let Some(accent) = token.accent {
+                                        PitchContour {
+                                            reading: token.reading.clone(),
+                                            accent,
+                                            display: reading_display,
+                                        }
+                                    } else {
+                                        div { class: "token-reading", "{display_reading(token, reading_display)}" }
+                                    }
+                                    div { class: "token-pos", "{pos_english(&token.pos.major)}" }
+                                    if let Some(level) = token.jlpt {
+                                        div { class: "token-jlpt {level.css_class()}", "{level.as_str()}" }
+                                    }
+                                    SpeakButton { text: token.surface.clone(), small: true }
+                                    if !token.glosses.is_empty() {
+                                        div { class: "gloss-popup",
+                                            for (sense, gloss) in token.glosses.iter().enumerate() {
+                                                div { class: "gloss-sense",
+                                                    span { class: "gloss-number", "{sense + 1}." }
+                                                    " {gloss}"
+                                                }
                                             }
                                         }
-                                        td {
-                                            if token.conjugation_type != "*" {
-                                                span { class: "conj-type", "{token.conjugation_type}" }
+                                    }
+                                }
+                            }
+                        }
+
+                        if !patterns.is_empty() {
+                            h3 { class: "detail-heading", "Grammar" }
+                            div { class: "grammar-list",
+                                for (index, pattern) in patterns.iter().enumerate() {
+                                    div {
+                                        class: if selected_pattern() == Some(index) {
+                                            "grammar-item grammar-item-selected"
+                                        } else {
+                                            "grammar-item"
+                                        },
+                                        onclick: move |_| {
+                                            if selected_pattern() == Some(index) {
+                                                selected_pattern.set(None);
+                                            } else {
+                                                selected_pattern.set(Some(index));
+                                            }
+                                        },
+                                        span { class: "grammar-name", "{pattern.name}" }
+                                        span { class: "grammar-surface", "{pattern.surface}" }
+                                        span { class: "grammar-explanation", "{pattern.explanation}" }
+                                    }
+                                }
+                            }
+                        }
+
+                        {
+                            let numerals = find_numerals(result);
+                            rsx! {
+                                if !numerals.is_empty() {
+                                    h3 { class: "detail-heading", "Numbers" }
+                                    div { class: "numeral-list",
+                                        for numeral in numerals {
+                                            div { class: "numeral-item",
+                                                span { class: "numeral-surface", "{numeral.surface}" }
+                                                span { class: "numeral-reading", "{numeral.reading}" }
+                                                span { class: "numeral-value", "{numeral.value}" }
+                                                if let Some(counter) = numeral.counter {
+                                                    span { class: "numeral-counter", "counter {counter}" }
+                                                }
                                             }
-                                            if token.conjugation_form != "*" {
-                                                span { class: "conj-form", " ({token.conjugation_form})" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        if let Some(literal) = selected_kanji() {
+                            KanjiPanel { literal, on_close: move |_| selected_kanji.set(None) }
+                        }
+
+                        if result.dependencies.nodes.len() > 1 {
+                            DependencyArcs { tree: result.dependencies.clone() }
+                        }
+
+                        {
+                            let chains = inflection_chains(result);
+                            rsx! {
+                                if !chains.is_empty() {
+                                    h3 { class: "detail-heading", "Inflections" }
+                                    div { class: "derivation-list",
+                                        for chain in chains {
+                                            div { class: "derivation",
+                                                span { class: "derivation-surface", "{chain.surface}" }
+                                                span { class: "derivation-lemma", "{chain.lemma}" }
+                                                for step in chain.steps {
+                                                    span { class: "derivation-arrow", "→" }
+                                                    span { class: "derivation-step",
+                                                        "{step.transformation.label()}"
+                                                        span { class: "derivation-morpheme", " ({step.surface})" }
+                                                    }
+                                                }
                                             }
                                         }
-                                        td {
-                                            SpeakButton { text: token.surface.clone(), small: true }
+                                    }
+                                }
+                            }
+                        }
+
+                        if let Some(paradigm) = selected_token()
+                            .and_then(|index| result.tokens.get(index))
+                            .and_then(token_paradigm)
+                        {
+                            ConjugationTable { paradigm }
+                        }
+
+                        h3 { class: "detail-heading", "Token Details" }
+                        div { class: "token-table-wrapper",
+                            table { class: "token-table",
+                                thead {
+                                    tr {
+                                        th { "Surface" }
+                                        th { "Reading" }
+                                        th { "Base Form" }
+                                        th { "POS" }
+                                        th { "POS Detail" }
+                                        th { "Conjugation" }
+                                        th { "Meaning" }
+                                        th { "" }
+                                    }
+                                }
+                                tbody {
+                                    for token in &result.tokens {
+                                        TokenRow {
+                                            token: token.clone(),
+                                            reading_display,
+                                            label_language,
                                         }
                                     }
                                 }
                             }
                         }
                     }
-                },
+                }
                 Some(Err(e)) => rsx! { p { class: "error", "Analysis error: {e}" } },
                 None => rsx! { p { class: "loading", "Analyzing sentence..." } },
             }
         }
     }
 }
+
+fn token_chip_class(token: &AnalyzedToken, selected: bool, highlighted: bool) -> String {
+    let mut class = format!("token-chip {}", pos_css_class(&token.pos.major));
+    if let Some(level) = token.jlpt {
+        class.push(' ');
+        class.push_str(level.css_class());
+    }
+    if selected {
+        class.push_str(" token-chip-selected");
+    }
+    if highlighted {
+        class.push_str(" token-chip-grammar");
+    }
+    class
+}
+
+/// A row of the token table, with POS and conjugation labels translated
+/// into `label_language`
+#[component]
+fn TokenRow(
+    token: AnalyzedToken,
+    reading_display: ReadingDisplay,
+    label_language: LabelLanguage,
+) -> Element {
+    let labels = LabelCatalog::builtin();
+    let major = labels.pos_major(&token.pos.major, label_language);
+    let subcategories = [&token.pos.sub1, &token.pos.sub2, &token.pos.sub3]
+        .into_iter()
+        .filter(|sub| sub.is_specified())
+        .map(|sub| (sub.to_string(), labels.pos_sub(sub, label_language).cloned()));
+
+    rsx! {
+        tr { class: pos_css_class(&token.pos.major),
+            td { class: "surface-cell", "{token.surface}" }
+            td { "{display_reading(&token, reading_display)}" }
+            td { "{token.base_form}" }
+            td {
+                span {
+                    class: "pos-badge {pos_css_class(&token.pos.major)}",
+                    title: major.map(|m| m.explanation.clone()).unwrap_or_default(),
+                    "{major.map_or(pos_english(&token.pos.major), |m| m.name.as_str())}"
+                }
+            }
+            td { class: "pos-detail",
+                for (original, text) in subcategories {
+                    TranslatedLabel { original, text }
+                }
+            }
+            td {
+                if token.conjugation_type.is_specified() {
+                    TranslatedLabel {
+                        original: token.conjugation_type.to_string(),
+                        text: labels.conjugation_type(&token.conjugation_type, label_language).cloned(),
+                        class: "conj-type",
+                    }
+                }
+                if token.conjugation_form.is_specified() {
+                    TranslatedLabel {
+                        original: token.conjugation_form.to_string(),
+                        text: labels.conjugation_form(&token.conjugation_form, label_language).cloned(),
+                        class: "conj-form",
+                    }
+                }
+            }
+            td { class: "gloss-cell",
+                for gloss in &token.glosses {
+                    div { "{gloss}" }
+                }
+            }
+            td {
+                SpeakButton { text: token.surface.clone(), small: true }
+            }
+        }
+    }
+}
+
+/// A dictionary label in the chosen language, followed by the Japanese
+/// original, with the explanation shown on hover
+#[component]
+fn TranslatedLabel(
+    original: String,
+    text: Option<LabelText>,
+    #[props(default)] class: String,
+) -> Element {
+    match text {
+        Some(text) => rsx! {
+            span { class: "translated-label {class}", title: "{text.explanation}",
+                "{text.name}"
+                span { class: "label-original", "{original}" }
+            }
+        },
+        None => rsx! {
+            span { class: "translated-label {class}", "{original}" }
+        },
+    }
+}
@@ -0,0 +1,38 @@
+use crate::server_fns::convert_sentence_style;
+use dioxus::prelude::*;
+use kumou_japanese::{Register, SpeechStyle};
+
+/// An utterance rewritten into the other style: plain utterances are shown
+/// polite and everything else plain
+#[component]
+pub fn StyleVersion(text: String, register: Option<Register>) -> Element {
+    let style = if register == Some(Register::Plain) {
+        SpeechStyle::Polite
+    } else {
+        SpeechStyle::Plain
+    };
+    let conversion =
+        use_server_future(use_reactive!(|text, style| convert_sentence_style(text, style)))?;
+
+    match &*conversion.read() {
+        Some(Ok(conversion)) => rsx! {
+            div { class: "style-version",
+                span { class: "style-version-label", "{style.label_ja()}" }
+                if conversion.changes.is_empty() {
+                    span { class: "style-version-unchanged",
+                        "No sentence-final predicate to convert"
+                    }
+                } else {
+                    span { class: "style-version-text", "{conversion.text}" }
+                    for change in &conversion.changes {
+                        span { class: "style-change",
+                            "{change.original} → {change.converted}"
+                        }
+                    }
+                }
+            }
+        },
+        Some(Err(e)) => rsx! { p { class: "error", "Conversion error: {e}" } },
+        None => rsx! {},
+    }
+}
@@ -23,6 +23,16 @@ const MAIN_CSS: Asset = asset!("/assets/styling/main.css");
 const TAILWIND_CSS: Asset = asset!("/assets/tailwind.css");
 
 fn main() {
+    // Set up logging before launch so the startup load below can report
+    dioxus::logger::initialize_default();
+
+    // Load the dictionaries up front so a broken user dictionary is
+    // reported at startup rather than on the first analysis request.
+    #[cfg(all(feature = "server", feature = "tokenizer"))]
+    if let Err(e) = kumou_japanese::Analyzer::shared() {
+        error!("Failed to load analyzer: {e}");
+    }
+
     dioxus::launch(App);
 }
 
@@ -1,5 +1,9 @@
 use dioxus::prelude::*;
-use kumou_japanese::{AnalyzedSentence, Dialogue, DialoguePage, TopicSummary};
+use kumou_japanese::{
+    AnalyzedSentence, Dialogue, DialogueAnalysis, DialoguePage, DifficultyLevel, DifficultySort,
+    JlptBreakdown, KanjiInfo, Register, SegmentationMode, SpeechStyle, StyleConversion,
+    TopicSummary,
+};
 
 const TOPIC1_JSON: &str = include_str!("../assets/data/japanese-daily-dialogue/topic1.json");
 const TOPIC2_JSON: &str = include_str!("../assets/data/japanese-daily-dialogue/topic2.json");
@@ -37,18 +41,125 @@ pub async fn get_topics() -> Result<Vec<TopicSummary>> {
     Ok(topics)
 }
 
+/// Fill in the difficulty of each dialogue and the difficulty and register of
+/// its utterances
+///
+/// Results are cached per dialogue, since every utterance has to be analyzed.
+/// Dialogues not cached yet are analyzed on a blocking thread, without
+/// holding the cache lock.
+#[cfg(all(feature = "server", feature = "tokenizer"))]
+async fn annotate_dialogues(dialogues: &mut [Dialogue]) -> Result<(), ServerFnError> {
+    use kumou_japanese::{AnalysisError, Difficulty, Register};
+    use std::collections::HashMap;
+    use std::sync::{Mutex, OnceLock};
+
+    /// Difficulty of a dialogue, and difficulty and register of each utterance
+    type Annotations = (Difficulty, Vec<(Difficulty, Register)>);
+    static ANNOTATIONS: OnceLock<Mutex<HashMap<u32, Annotations>>> = OnceLock::new();
+
+    let cache = ANNOTATIONS.get_or_init(Default::default);
+    let lock = || cache.lock().map_err(|e| ServerFnError::new(e.to_string()));
+
+    let missing: Vec<Dialogue> = {
+        let annotations = lock()?;
+        dialogues
+            .iter()
+            .filter(|d| !annotations.contains_key(&d.dialogue_id))
+            .cloned()
+            .collect()
+    };
+    if !missing.is_empty() {
+        let analyzed = tokio::task::spawn_blocking(move || {
+            let analyzer = kumou_japanese::Analyzer::shared()?;
+            let analyses = analyzer.analyze_dialogues(&missing)?;
+            let annotations: Vec<(u32, Annotations)> = missing
+                .iter()
+                .map(|dialogue| {
+                    let analysis = analyses.get(&dialogue.dialogue_id);
+                    let utterances: Vec<(Difficulty, Register)> = dialogue
+                        .utterances
+                        .iter()
+                        .map(|u| {
+                            let sentences = analysis
+                                .and_then(|a| a.utterances.get(&u.turn_num))
+                                .map_or(&[][..], Vec::as_slice);
+                            annotate_utterance(sentences, analyzer.jlpt())
+                        })
+                        .collect();
+                    let difficulties: Vec<Difficulty> =
+                        utterances.iter().map(|(d, _)| *d).collect();
+                    (
+                        dialogue.dialogue_id,
+                        (Difficulty::of_dialogue(&difficulties), utterances),
+                    )
+                })
+                .collect();
+            Ok::<_, AnalysisError>(annotations)
+        })
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        lock()?.extend(analyzed);
+    }
+
+    let annotations = lock()?;
+    for dialogue in dialogues {
+        let Some((overall, utterances)) = annotations.get(&dialogue.dialogue_id) else {
+            continue;
+        };
+        dialogue.difficulty = Some(*overall);
+        for (utterance, (difficulty, register)) in dialogue.utterances.iter_mut().zip(utterances) {
+            utterance.difficulty = Some(*difficulty);
+            utterance.register = Some(*register);
+        }
+    }
+    Ok(())
+}
+
+/// Difficulty and register of an utterance from the analyses of its sentences
+///
+/// The difficulty is the average of the sentences, and the register the most
+/// formal one among them.
+#[cfg(all(feature = "server", feature = "tokenizer"))]
+fn annotate_utterance(
+    sentences: &[AnalyzedSentence],
+    jlpt: Option<&kumou_japanese::JlptLists>,
+) -> (kumou_japanese::Difficulty, Register) {
+    use kumou_japanese::{Difficulty, classify_register};
+
+    let difficulties: Vec<Difficulty> = sentences
+        .iter()
+        .map(|s| Difficulty::of_sentence(s, jlpt))
+        .collect();
+    let registers: Vec<Register> = sentences.iter().map(classify_register).collect();
+    let register = [Register::Respectful, Register::Humble, Register::Polite]
+        .into_iter()
+        .find(|r| registers.contains(r))
+        .unwrap_or(Register::Plain);
+    (Difficulty::of_dialogue(&difficulties), register)
+}
+
+/// Without the tokenizer nothing can be analyzed, so dialogues stay unannotated
+#[cfg(not(all(feature = "server", feature = "tokenizer")))]
+async fn annotate_dialogues(_dialogues: &mut [Dialogue]) -> Result<(), ServerFnError> {
+    Ok(())
+}
+
 #[post("/api/dialogues_by_topic")]
 pub async fn get_dialogues_by_topic(
     topic_id: u32,
     page: usize,
     per_page: usize,
     search: String,
+    sort: DifficultySort,
+    level: Option<DifficultyLevel>,
+    register: Option<Register>,
 ) -> Result<DialoguePage> {
     let dialogues = load_all_dialogues()?;
 
     let per_page = if per_page == 0 { 20 } else { per_page.min(100) };
 
-    let filtered: Vec<Dialogue> = dialogues
+    let mut filtered: Vec<Dialogue> = dialogues
         .into_iter()
         .filter(|d| d.topic_id == topic_id)
         .filter(|d| {
@@ -62,8 +173,31 @@ pub async fn get_dialogues_by_topic(
         })
         .collect();
 
+    // Scores are only needed to sort or filter, corpus order can skip them
+    let needs_scores = sort != DifficultySort::None || level.is_some() || register.is_some();
+    if needs_scores && let Err(e) = annotate_dialogues(&mut filtered).await {
+        error!("Failed to annotate dialogues of topic {topic_id}: {e}");
+    }
+    // Unannotated dialogues are kept, the filters cannot judge them
+    if let Some(level) = level {
+        filtered.retain(|d| d.difficulty.is_none_or(|difficulty| difficulty.level() == level));
+    }
+    if let Some(register) = register {
+        // A dialogue matches when any of its utterances is in the register
+        filtered.retain(|d| {
+            d.utterances.iter().all(|u| u.register.is_none())
+                || d.utterances.iter().any(|u| u.register == Some(register))
+        });
+    }
+    let score = |d: &Dialogue| d.difficulty.map_or(0.0, |difficulty| difficulty.score);
+    match sort {
+        DifficultySort::None => {}
+        DifficultySort::EasiestFirst => filtered.sort_by(|a, b| score(a).total_cmp(&score(b))),
+        DifficultySort::HardestFirst => filtered.sort_by(|a, b| score(b).total_cmp(&score(a))),
+    }
+
     let total = filtered.len();
-    let total_pages = (total + per_page - 1) / per_page;
+    let total_pages = total.div_ceil(per_page);
     let page = page.min(total_pages.saturating_sub(1));
 
     let start = page * per_page;
@@ -82,32 +216,159 @@ pub async fn get_dialogues_by_topic(
     })
 }
 
-#[post("/api/dialogue")]
-pub async fn get_dialogue(dialogue_id: u32) -> Result<Dialogue> {
-    let dialogues = load_all_dialogues()?;
-
-    Ok(dialogues
+fn find_dialogue(dialogue_id: u32) -> Result<Dialogue, ServerFnError> {
+    load_all_dialogues()?
         .into_iter()
         .find(|d| d.dialogue_id == dialogue_id)
-        .ok_or_else(|| ServerFnError::new("Dialogue not found"))?)
+        .ok_or_else(|| ServerFnError::new("Dialogue not found"))
+}
+
+#[post("/api/dialogue")]
+pub async fn get_dialogue(dialogue_id: u32) -> Result<Dialogue> {
+    let mut dialogue = find_dialogue(dialogue_id)?;
+    // The dialogue is still worth showing without difficulty and register
+    if let Err(e) = annotate_dialogues(std::slice::from_mut(&mut dialogue)).await {
+        error!("Failed to annotate dialogue {dialogue_id}: {e}");
+    }
+    Ok(dialogue)
 }
 
 #[post("/api/analyze")]
-pub async fn analyze_sentence(text: String) -> Result<AnalyzedSentence> {
+pub async fn analyze_sentence(text: String, mode: SegmentationMode) -> Result<AnalyzedSentence> {
     #[cfg(feature = "tokenizer")]
     {
-        let tokenizer = kumou_japanese::create_tokenizer()
+        let analyzer = kumou_japanese::Analyzer::shared()
             .map_err(|e| ServerFnError::new(e.to_string()))?;
 
-        Ok(kumou_japanese::analyze_sentence(&tokenizer, &text)
+        Ok(analyzer
+            .analyze_with_mode(&text, mode)
             .map_err(|e| ServerFnError::new(e.to_string()))?)
     }
 
     #[cfg(not(feature = "tokenizer"))]
     {
-        let _ = text;
+        let _ = (text, mode);
+        Err(ServerFnError::new(
+            "Tokenizer not available: build with 'tokenizer' feature to enable sentence analysis",
+        ).into())
+    }
+}
+
+#[post("/api/analyze_dialogue")]
+pub async fn analyze_dialogue(
+    dialogue_id: u32,
+    mode: SegmentationMode,
+) -> Result<DialogueAnalysis> {
+    #[cfg(feature = "tokenizer")]
+    {
+        let analyzer = kumou_japanese::Analyzer::shared()
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        let dialogue = find_dialogue(dialogue_id)?;
+
+        // Analysis blocks on its own threads, keep it off the async runtime
+        let mut analyses = tokio::task::spawn_blocking(move || {
+            analyzer.analyze_dialogues_with_mode(std::slice::from_ref(&dialogue), mode)
+        })
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .map_err(|e| ServerFnError::new(e.to_string()))?;
+        Ok(analyses.remove(&dialogue_id).unwrap_or_default())
+    }
+
+    #[cfg(not(feature = "tokenizer"))]
+    {
+        let _ = (dialogue_id, mode);
         Err(ServerFnError::new(
             "Tokenizer not available: build with 'tokenizer' feature to enable sentence analysis",
         ).into())
     }
 }
+
+#[post("/api/convert_style")]
+pub async fn convert_sentence_style(text: String, style: SpeechStyle) -> Result<StyleConversion> {
+    #[cfg(feature = "tokenizer")]
+    {
+        let analyzer = kumou_japanese::Analyzer::shared()
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        let sentence = analyzer
+            .analyze(&text)
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+
+        Ok(kumou_japanese::convert_style(&sentence, style))
+    }
+
+    #[cfg(not(feature = "tokenizer"))]
+    {
+        let _ = (text, style);
+        Err(ServerFnError::new(
+            "Tokenizer not available: build with 'tokenizer' feature to enable style conversion",
+        ).into())
+    }
+}
+
+#[post("/api/dialogue_levels")]
+pub async fn get_dialogue_levels(dialogue_id: u32) -> Result<JlptBreakdown> {
+    #[cfg(feature = "tokenizer")]
+    {
+        let analyzer = kumou_japanese::Analyzer::shared()
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        if analyzer.jlpt().is_none() {
+            return Err(ServerFnError::new(format!(
+                "JLPT lists not configured: set {} or {}",
+                kumou_japanese::JLPT_VOCABULARY_ENV,
+                kumou_japanese::JLPT_KANJI_ENV,
+            ))
+            .into());
+        }
+        let dialogue = find_dialogue(dialogue_id)?;
+
+        // Analysis blocks, keep it off the async runtime
+        Ok(tokio::task::spawn_blocking(move || {
+            let analysis = analyzer.analyze_dialogue(&dialogue)?;
+            let sentences: Vec<AnalyzedSentence> =
+                analysis.utterances.into_values().flatten().collect();
+            Ok::<_, kumou_japanese::AnalysisError>(
+                analyzer.jlpt().map(|lists| lists.breakdown(&sentences)).unwrap_or_default(),
+            )
+        })
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .map_err(|e| ServerFnError::new(e.to_string()))?)
+    }
+
+    #[cfg(not(feature = "tokenizer"))]
+    {
+        let _ = dialogue_id;
+        Err(ServerFnError::new(
+            "Tokenizer not available: build with 'tokenizer' feature to enable level breakdowns",
+        ).into())
+    }
+}
+
+#[post("/api/kanji")]
+pub async fn get_kanji(literal: char) -> Result<KanjiInfo> {
+    #[cfg(feature = "tokenizer")]
+    {
+        let analyzer = kumou_japanese::Analyzer::shared()
+            .map_err(|e| ServerFnError::new(e.to_string()))?;
+        let dictionary = analyzer.kanjidic().ok_or_else(|| {
+            ServerFnError::new(format!(
+                "Kanji dictionary not configured: set {}",
+                kumou_japanese::KANJIDIC_ENV
+            ))
+        })?;
+
+        Ok(dictionary
+            .get(literal)
+            .cloned()
+            .ok_or_else(|| ServerFnError::new(format!("No entry for {literal}")))?)
+    }
+
+    #[cfg(not(feature = "tokenizer"))]
+    {
+        let _ = literal;
+        Err(ServerFnError::new(
+            "Tokenizer not available: build with 'tokenizer' feature to enable kanji lookups",
+        ).into())
+    }
+}
@@ -1,15 +1,37 @@
 use crate::Route;
-use crate::components::{SentenceAnalysis, SpeakButton};
-use crate::server_fns::get_dialogue;
+use crate::components::{
+    FuriganaText, LevelBreakdown, SentenceAnalysis, SpeakButton, StyleVersion,
+};
+use crate::server_fns::{analyze_dialogue, get_dialogue};
 use dioxus::prelude::*;
-use kumou_japanese::topic_name_ja;
+use kumou_japanese::{
+    AnalyzedSentence, LabelLanguage, ReadingDisplay, SegmentationMode, SentenceSpan,
+    split_sentences, topic_name_ja,
+};
 
 const DIALOGUE_CSS: Asset = asset!("/assets/styling/dialogue.css");
 
 #[component]
 pub fn DialogueDetail(dialogue_id: u32) -> Element {
     let dialogue = use_server_future(move || get_dialogue(dialogue_id))?;
-    let mut selected_sentence = use_signal(|| Option::<String>::None);
+    // Every sentence analyzed in one request, without holding up the page
+    let prefetch_mode = SegmentationMode::default();
+    let analysis = use_resource(move || analyze_dialogue(dialogue_id, prefetch_mode));
+    let sentence_analyses = move |turn_num: u32| -> Vec<AnalyzedSentence> {
+        analysis
+            .read()
+            .as_ref()
+            .and_then(|result| result.as_ref().ok())
+            .and_then(|analysis| analysis.utterances.get(&turn_num).cloned())
+            .unwrap_or_default()
+    };
+    // Utterance and sentence index of the sentence being analyzed
+    let mut selected_sentence = use_signal(|| Option::<(usize, usize)>::None);
+    let mut hovered_token = use_signal(|| Option::<(usize, usize)>::None);
+    let mut show_furigana = use_signal(|| false);
+    let mut show_other_style = use_signal(|| false);
+    let mut reading_display = use_signal(ReadingDisplay::default);
+    let mut label_language = use_signal(LabelLanguage::default);
 
     rsx! {
         document::Link { rel: "stylesheet", href: DIALOGUE_CSS }
@@ -30,32 +52,111 @@ pub fn DialogueDetail(dialogue_id: u32) -> Element {
                         "{topic_name_ja(&dialogue.topic_name)} ({dialogue.topic_name})"
                     }
 
+                    LevelBreakdown { dialogue_id }
+
+                    div { class: "display-toggles",
+                        label { class: "toggle-label",
+                            input {
+                                r#type: "checkbox",
+                                checked: show_furigana(),
+                                onchange: move |e| show_furigana.set(e.checked()),
+                            }
+                            " Show furigana"
+                        }
+                        label { class: "toggle-label",
+                            input {
+                                r#type: "checkbox",
+                                checked: show_other_style(),
+                                onchange: move |e| show_other_style.set(e.checked()),
+                            }
+                            " Show plain/polite version"
+                        }
+                        label { class: "toggle-label",
+                            "Readings "
+                            select {
+                                class: "toggle-select",
+                                onchange: move |e| {
+                                    let value = e.value();
+                                    if let Some(display) = ReadingDisplay::ALL
+                                        .into_iter()
+                                        .find(|d| d.as_str() == value)
+                                    {
+                                        reading_display.set(display);
+                                    }
+                                },
+                                for display in ReadingDisplay::ALL {
+                                    option {
+                                        value: display.as_str(),
+                                        selected: display == reading_display(),
+                                        "{display.label()}"
+                                    }
+                                }
+                            }
+                        }
+                        label { class: "toggle-label",
+                            "Grammar labels "
+                            select {
+                                class: "toggle-select",
+                                onchange: move |e| {
+                                    if let Ok(language) = e.value().parse() {
+                                        label_language.set(language);
+                                    }
+                                },
+                                for language in LabelLanguage::ALL {
+                                    option {
+                                        value: language.as_str(),
+                                        selected: language == label_language(),
+                                        "{language.label()}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
                     div { class: "dialogue-conversation",
-                        for utterance in &dialogue.utterances {
+                        for (utterance_index, utterance) in dialogue.utterances.iter().enumerate() {
                             div {
                                 class: "utterance utterance-{utterance.speaker}",
                                 div { class: "utterance-bubble",
                                     div { class: "speaker-label speaker-{utterance.speaker}",
                                         "Speaker {utterance.speaker}"
+                                        if let Some(register) = utterance.register {
+                                            span {
+                                                class: "register-badge register-{register.as_str()}",
+                                                title: "{register.label()}",
+                                                "{register.label_ja()}"
+                                            }
+                                        }
                                     }
                                     div { class: "utterance-content",
-                                        p {
-                                            class: "utterance-text",
-                                            onclick: {
-                                                let text = utterance.utterance.clone();
-                                                move |_| {
-                                                    let current = selected_sentence();
-                                                    if current.as_deref() == Some(text.as_str()) {
+                                        p { class: "utterance-text",
+                                            UtteranceSentences {
+                                                text: utterance.utterance.clone(),
+                                                selected: selected_sentence()
+                                                    .filter(|(index, _)| *index == utterance_index)
+                                                    .map(|(_, sentence)| sentence),
+                                                highlight: hovered_token(),
+                                                furigana: show_furigana(),
+                                                prefetched: sentence_analyses(utterance.turn_num),
+                                                on_select: move |sentence| {
+                                                    let position = (utterance_index, sentence);
+                                                    if selected_sentence() == Some(position) {
                                                         selected_sentence.set(None);
                                                     } else {
-                                                        selected_sentence.set(Some(text.clone()));
+                                                        selected_sentence.set(Some(position));
                                                     }
-                                                }
-                                            },
-                                            "{utterance.utterance}"
+                                                    hovered_token.set(None);
+                                                },
+                                            }
                                         }
                                         SpeakButton { text: utterance.utterance.clone() }
                                     }
+                                    if show_other_style() {
+                                        StyleVersion {
+                                            text: utterance.utterance.clone(),
+                                            register: utterance.register,
+                                        }
+                                    }
                                 }
                             }
                         }
@@ -63,8 +164,22 @@ pub fn DialogueDetail(dialogue_id: u32) -> Element {
 
                     p { class: "hint-text", "Click any sentence to analyze its structure" }
 
-                    if let Some(sentence) = selected_sentence() {
-                        SentenceAnalysis { text: sentence }
+                    if let Some((turn_num, index, sentence)) =
+                        selected_sentence().and_then(|(utterance, index)| {
+                            let utterance = dialogue.utterances.get(utterance)?;
+                            let sentence = split_sentences(&utterance.utterance).into_iter().nth(index)?;
+                            Some((utterance.turn_num, index, sentence))
+                        })
+                    {
+                        SentenceAnalysis {
+                            key: "{turn_num}-{index}",
+                            text: sentence.text,
+                            prefetched: sentence_analyses(turn_num).into_iter().nth(index),
+                            prefetched_mode: prefetch_mode,
+                            reading_display: reading_display(),
+                            label_language: label_language(),
+                            on_token_hover: move |span| hovered_token.set(span),
+                        }
                     }
                 },
                 Some(Err(e)) => rsx! { p { class: "error", "Error: {e}" } },
@@ -73,3 +188,69 @@ pub fn DialogueDetail(dialogue_id: u32) -> Element {
         }
     }
 }
+
+/// The sentences of an utterance, each clickable, with furigana or the
+/// hovered token of the selected sentence highlighted
+#[component]
+fn UtteranceSentences(
+    text: String,
+    selected: Option<usize>,
+    highlight: Option<(usize, usize)>,
+    furigana: bool,
+    /// Analyses of the sentences, when already fetched
+    prefetched: Vec<AnalyzedSentence>,
+    on_select: EventHandler<usize>,
+) -> Element {
+    // Each sentence with the whitespace before it
+    let mut gap_start = 0;
+    let parts: Vec<(String, SentenceSpan)> = split_sentences(&text)
+        .into_iter()
+        .map(|sentence| {
+            let gap = text[gap_start..sentence.byte_start].to_string();
+            gap_start = sentence.byte_end;
+            (gap, sentence)
+        })
+        .collect();
+
+    rsx! {
+        for (index, (gap, sentence)) in parts.into_iter().enumerate() {
+            "{gap}"
+            span {
+                class: if selected == Some(index) {
+                    "utterance-sentence utterance-sentence-selected"
+                } else {
+                    "utterance-sentence"
+                },
+                onclick: move |_| on_select.call(index),
+                {
+                    let highlight = highlight.filter(|_| selected == Some(index));
+                    if furigana {
+                        rsx! {
+                            FuriganaText {
+                                text: sentence.text.clone(),
+                                highlight,
+                                prefetched: prefetched.get(index).cloned(),
+                            }
+                        }
+                    } else if let Some((start, end)) = highlight {
+                        let (before, token, after) = split_chars(&sentence.text, start, end);
+                        rsx! {
+                            "{before}"
+                            mark { class: "token-highlight", "{token}" }
+                            "{after}"
+                        }
+                    } else {
+                        rsx! { "{sentence.text}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Split `text` into the parts before, inside and after the char range
+fn split_chars(text: &str, start: usize, end: usize) -> (&str, &str, &str) {
+    let byte = |chars: usize| text.char_indices().nth(chars).map_or(text.len(), |(i, _)| i);
+    let (start, end) = (byte(start), byte(end.max(start)));
+    (&text[..start], &text[start..end], &text[end..])
+}
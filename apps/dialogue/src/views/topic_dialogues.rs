@@ -1,21 +1,29 @@
 use crate::Route;
 use crate::server_fns::get_dialogues_by_topic;
 use dioxus::prelude::*;
-use kumou_japanese::topic_name_ja;
+use kumou_japanese::{DifficultyLevel, DifficultySort, Register, topic_name_ja};
 
 const DIALOGUE_CSS: Asset = asset!("/assets/styling/dialogue.css");
 
 #[component]
 pub fn TopicDialogues(topic_id: u32) -> Element {
     let mut current_page = use_signal(|| 0usize);
-    let mut search_input = use_signal(|| String::new());
-    let mut active_search = use_signal(|| String::new());
+    let mut search_input = use_signal(String::new);
+    let mut active_search = use_signal(String::new);
+    let mut sort = use_signal(DifficultySort::default);
+    let mut level = use_signal(|| Option::<DifficultyLevel>::None);
+    let mut register = use_signal(|| Option::<Register>::None);
     let per_page = 20usize;
 
     let dialogues = use_server_future(move || {
         let search = active_search();
         let page = current_page();
-        async move { get_dialogues_by_topic(topic_id, page, per_page, search).await }
+        let sort = sort();
+        let level = level();
+        let register = register();
+        async move {
+            get_dialogues_by_topic(topic_id, page, per_page, search, sort, level, register).await
+        }
     })?;
 
     rsx! {
@@ -77,6 +85,76 @@ pub fn TopicDialogues(topic_id: u32) -> Element {
                             }
                         }
 
+                        div { class: "display-toggles",
+                            label { class: "toggle-label",
+                                "Order "
+                                select {
+                                    class: "toggle-select",
+                                    onchange: move |e| {
+                                        let value = e.value();
+                                        if let Some(selected) = DifficultySort::ALL
+                                            .into_iter()
+                                            .find(|s| s.as_str() == value)
+                                        {
+                                            sort.set(selected);
+                                            current_page.set(0);
+                                        }
+                                    },
+                                    for option_sort in DifficultySort::ALL {
+                                        option {
+                                            value: option_sort.as_str(),
+                                            selected: option_sort == sort(),
+                                            "{option_sort.label()}"
+                                        }
+                                    }
+                                }
+                            }
+                            label { class: "toggle-label",
+                                "Difficulty "
+                                select {
+                                    class: "toggle-select",
+                                    onchange: move |e| {
+                                        let value = e.value();
+                                        level.set(
+                                            DifficultyLevel::ALL
+                                                .into_iter()
+                                                .find(|l| l.as_str() == value),
+                                        );
+                                        current_page.set(0);
+                                    },
+                                    option { value: "all", selected: level().is_none(), "All" }
+                                    for option_level in DifficultyLevel::ALL {
+                                        option {
+                                            value: option_level.as_str(),
+                                            selected: Some(option_level) == level(),
+                                            "{option_level.label()}"
+                                        }
+                                    }
+                                }
+                            }
+                            label { class: "toggle-label",
+                                "Register "
+                                select {
+                                    class: "toggle-select",
+                                    onchange: move |e| {
+                                        let value = e.value();
+                                        register.set(
+                                            Register::ALL.into_iter().find(|r| r.as_str() == value),
+                                        );
+                                        current_page.set(0);
+                                    },
+                                    option { value: "all", selected: register().is_none(), "All" }
+                                    for option_register in Register::ALL {
+                                        option {
+                                            value: option_register.as_str(),
+                                            selected: Some(option_register) == register(),
+                                            "{option_register.label_ja()} ({option_register.label()})"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
                         // Dialogue list
                         div { class: "dialogue-list",
                             for dialogue in &page_data.dialogues {
@@ -85,6 +163,13 @@ pub fn TopicDialogues(topic_id: u32) -> Element {
                                     class: "dialogue-card",
                                     div { class: "dialogue-header",
                                         span { class: "dialogue-id", "Dialogue #{dialogue.dialogue_id}" }
+                                        if let Some(difficulty) = dialogue.difficulty {
+                                            span {
+                                                class: "difficulty-badge difficulty-{difficulty.level().as_str()}",
+                                                title: "Difficulty score out of 100",
+                                                "{difficulty.level().label()} · {difficulty.score:.0}"
+                                            }
+                                        }
                                         span { class: "dialogue-turns", "{dialogue.dialogue_length} turns" }
                                     }
                                     div { class: "dialogue-preview",
@@ -7,7 +7,7 @@ const DIALOGUE_CSS: Asset = asset!("/assets/styling/dialogue.css");
 
 #[component]
 pub fn TopicList() -> Element {
-    let topics = use_server_future(move || get_topics())?;
+    let topics = use_server_future(get_topics)?;
 
     rsx! {
         document::Link { rel: "stylesheet", href: DIALOGUE_CSS }
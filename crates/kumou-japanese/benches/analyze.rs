@@ -0,0 +1,58 @@
+//! Per-request analysis latency: rebuilding the tokenizer on every call
+//! versus reusing the shared [`Analyzer`], and analyzing in parallel.
+//!
+//! Run with `cargo bench -p kumou-japanese --features tokenizer`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use kumou_japanese::{Analyzer, analyze_sentence, create_tokenizer};
+
+const SENTENCES: &[&str] = &[
+    "おはようございます。今日はいい天気ですね。",
+    "散歩に行きませんか。",
+    "昨日は友達と一緒に映画を見に行きました。",
+    "東京国際空港から電車で三十分ぐらいかかります。",
+];
+
+const ROUNDS: u32 = 20;
+
+fn report(label: &str, elapsed: Duration, requests: u32) {
+    let per_request = elapsed / requests;
+    println!("{label:<28} {requests:>5} requests  {per_request:>12.3?} / request");
+}
+
+fn main() {
+    let requests = ROUNDS * SENTENCES.len() as u32;
+
+    // Before: every request loads the dictionary and builds a tokenizer.
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for text in SENTENCES {
+            let tokenizer = create_tokenizer().expect("tokenizer");
+            black_box(analyze_sentence(&tokenizer, text).expect("analysis"));
+        }
+    }
+    report("create_tokenizer per call", start.elapsed(), requests);
+
+    // After: the first request pays for loading, later ones reuse it.
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for text in SENTENCES {
+            let analyzer = Analyzer::shared().expect("analyzer");
+            black_box(analyzer.analyze(text).expect("analysis"));
+        }
+    }
+    report("Analyzer::shared", start.elapsed(), requests);
+
+    // Batch: every sentence of every round at once, spread over threads.
+    let analyzer = Analyzer::shared().expect("analyzer");
+    let texts: Vec<&str> = (0..ROUNDS).flat_map(|_| SENTENCES.iter().copied()).collect();
+    let start = Instant::now();
+    black_box(
+        analyzer
+            .analyze_parallel(&texts, analyzer.config().mode)
+            .expect("analysis"),
+    );
+    report("Analyzer::analyze_parallel", start.elapsed(), requests);
+}
@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AnalysisError;
+use crate::kana::{is_katakana, split_morae};
+use crate::tokenizer::{AnalyzedSentence, AnalyzedToken};
+
+/// Tokyo pitch accent type, named by where the pitch falls
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum AccentPattern {
+    /// 平板: no fall, a following particle stays high
+    Heiban,
+    /// 頭高: falls after the first mora
+    Atamadaka,
+    /// 中高: falls after a middle mora
+    Nakadaka,
+    /// 尾高: falls after the last mora, onto a following particle
+    Odaka,
+}
+
+impl AccentPattern {
+    /// English label
+    pub fn label(&self) -> &'static str {
+        match self {
+            AccentPattern::Heiban => "Heiban",
+            AccentPattern::Atamadaka => "Atamadaka",
+            AccentPattern::Nakadaka => "Nakadaka",
+            AccentPattern::Odaka => "Odaka",
+        }
+    }
+
+    /// Japanese label
+    pub fn label_ja(&self) -> &'static str {
+        match self {
+            AccentPattern::Heiban => "平板",
+            AccentPattern::Atamadaka => "頭高",
+            AccentPattern::Nakadaka => "中高",
+            AccentPattern::Odaka => "尾高",
+        }
+    }
+}
+
+/// Pitch accent of a word
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct PitchAccent {
+    /// Mora after which the pitch falls (アクセント核), 0 for none
+    pub nucleus: usize,
+    /// Number of morae in the reading
+    pub morae: usize,
+}
+
+impl PitchAccent {
+    /// Accent type implied by where the nucleus falls
+    pub fn pattern(&self) -> AccentPattern {
+        match self.nucleus {
+            0 => AccentPattern::Heiban,
+            1 => AccentPattern::Atamadaka,
+            n if n < self.morae => AccentPattern::Nakadaka,
+            _ => AccentPattern::Odaka,
+        }
+    }
+
+    /// High (`true`) or low pitch of each mora, followed by the pitch of an
+    /// attached particle
+    ///
+    /// The first mora is low unless it carries the nucleus; pitch then stays
+    /// high up to the nucleus and is low after it.
+    pub fn pitches(&self) -> Vec<bool> {
+        (1..=self.morae + 1)
+            .map(|mora| match self.nucleus {
+                0 => mora > 1,
+                1 => mora == 1,
+                n => mora > 1 && mora <= n,
+            })
+            .collect()
+    }
+}
+
+/// Local pitch accent dictionary, keyed by lemma and reading
+///
+/// The CSV format has one entry per line with three columns:
+///
+/// ```text
+/// # lemma,reading,nucleus
+/// 箸,ハシ,1
+/// 橋,ハシ,2
+/// 端,ハシ,0
+/// ```
+///
+/// The nucleus is the mora after which pitch falls, 0 for heiban. Blank
+/// lines and lines starting with `#` are ignored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccentDictionary {
+    entries: HashMap<(String, String), usize>,
+}
+
+impl AccentDictionary {
+    /// Parse and validate an accent dictionary from CSV text
+    pub fn parse(csv: &str) -> Result<Self, AnalysisError> {
+        let mut entries = HashMap::new();
+
+        for (index, raw) in csv.lines().enumerate() {
+            let line = index + 1;
+            let raw = raw.trim();
+            if raw.is_empty() || raw.starts_with('#') {
+                continue;
+            }
+
+            let columns: Vec<&str> = raw.split(',').map(str::trim).collect();
+            let [lemma, reading, nucleus] = columns[..] else {
+                return Err(AnalysisError::AccentDictionaryColumns {
+                    line,
+                    found: columns.len(),
+                });
+            };
+
+            if reading.is_empty() || !reading.chars().all(is_katakana) {
+                return Err(AnalysisError::AccentDictionaryReading {
+                    line,
+                    reading: reading.to_string(),
+                });
+            }
+            let morae = split_morae(reading).len();
+            let nucleus = nucleus
+                .parse::<usize>()
+                .ok()
+                .filter(|&n| n <= morae)
+                .ok_or_else(|| AnalysisError::AccentDictionaryNucleus {
+                    line,
+                    nucleus: nucleus.to_string(),
+                })?;
+
+            let key = (lemma.to_string(), reading.to_string());
+            if entries.insert(key, nucleus).is_some() {
+                return Err(AnalysisError::AccentDictionaryDuplicate {
+                    line,
+                    lemma: lemma.to_string(),
+                    reading: reading.to_string(),
+                });
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Read and validate an accent dictionary CSV file
+    pub fn load(path: &Path) -> Result<Self, AnalysisError> {
+        let csv = std::fs::read_to_string(path).map_err(|e| {
+            AnalysisError::AccentDictionaryRead(format!("{}: {e}", path.display()))
+        })?;
+        Self::parse(&csv)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Accent of `lemma` read as `reading` (katakana)
+    pub fn lookup(&self, lemma: &str, reading: &str) -> Option<PitchAccent> {
+        let nucleus = *self
+            .entries
+            .get(&(lemma.to_string(), reading.to_string()))?;
+        Some(PitchAccent {
+            nucleus,
+            morae: split_morae(reading).len(),
+        })
+    }
+
+    /// Accent of `token` as written
+    ///
+    /// Only tokens in their dictionary form match, since the dictionary
+    /// reading is that of the lemma; 食べる is found but 食べ is not.
+    pub fn token_accent(&self, token: &AnalyzedToken) -> Option<PitchAccent> {
+        if token.reading == "*" {
+            return None;
+        }
+        let lemma = if token.base_form == "*" {
+            &token.surface
+        } else {
+            &token.base_form
+        };
+        if lemma != &token.surface {
+            return None;
+        }
+        self.lookup(lemma, &token.reading)
+    }
+
+    /// Fill in the accent of every token found in the dictionary
+    pub fn annotate(&self, sentence: &mut AnalyzedSentence) {
+        for token in &mut sentence.tokens {
+            token.accent = self.token_accent(token);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::token;
+
+    const DICTIONARY: &str = "# lemma,reading,nucleus\n\
+                              箸,ハシ,1\n\
+                              橋,ハシ,2\n\
+                              \n\
+                              端,ハシ,0\n\
+                              卵,タマゴ,2\n\
+                              今日,キョウ,1\n\
+                              コーヒー,コーヒー,3\n\
+                              食べる,タベル,2\n";
+
+    #[test]
+    fn parse_and_lookup() {
+        let dictionary = AccentDictionary::parse(DICTIONARY).unwrap();
+        assert_eq!(dictionary.len(), 7);
+        let accent = |lemma: &str, reading: &str| dictionary.lookup(lemma, reading);
+        assert_eq!(accent("箸", "ハシ"), Some(PitchAccent { nucleus: 1, morae: 2 }));
+        assert_eq!(accent("端", "ハシ"), Some(PitchAccent { nucleus: 0, morae: 2 }));
+        // ョ shares a mora with キ, while ー is a mora of its own
+        assert_eq!(accent("今日", "キョウ"), Some(PitchAccent { nucleus: 1, morae: 2 }));
+        assert_eq!(accent("コーヒー", "コーヒー"), Some(PitchAccent { nucleus: 3, morae: 4 }));
+        assert_eq!(accent("箸", "ハシラ"), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |csv: &str| AccentDictionary::parse(csv).unwrap_err();
+        assert!(matches!(
+            error("箸,ハシ"),
+            AnalysisError::AccentDictionaryColumns { line: 1, found: 2 }
+        ));
+        for reading in ["", "はし", "箸"] {
+            assert!(
+                matches!(
+                    error(&format!("# header\n箸,{reading},1")),
+                    AnalysisError::AccentDictionaryReading { line: 2, .. }
+                ),
+                "{reading}"
+            );
+        }
+        for nucleus in ["3", "-1", "one", ""] {
+            assert!(
+                matches!(
+                    error(&format!("箸,ハシ,{nucleus}")),
+                    AnalysisError::AccentDictionaryNucleus { line: 1, .. }
+                ),
+                "{nucleus}"
+            );
+        }
+        // キョウ is two morae, so 3 is out of range
+        assert!(matches!(
+            error("今日,キョウ,3"),
+            AnalysisError::AccentDictionaryNucleus { line: 1, .. }
+        ));
+        assert!(matches!(
+            error("箸,ハシ,1\n箸,ハシ,2"),
+            AnalysisError::AccentDictionaryDuplicate { line: 2, .. }
+        ));
+    }
+
+    /// Pitches as H and L, the attached particle last
+    fn contour(nucleus: usize, morae: usize) -> String {
+        PitchAccent { nucleus, morae }
+            .pitches()
+            .into_iter()
+            .map(|high| if high { 'H' } else { 'L' })
+            .collect()
+    }
+
+    #[test]
+    fn patterns_and_pitches() {
+        let cases = [
+            // 端(が)
+            (0, 2, AccentPattern::Heiban, "LHH"),
+            // 箸(が)
+            (1, 2, AccentPattern::Atamadaka, "HLL"),
+            // 今日(が): キョ is one mora
+            (1, 2, AccentPattern::Atamadaka, "HLL"),
+            // 卵(が)
+            (2, 3, AccentPattern::Nakadaka, "LHLL"),
+            // コーヒー(が): ー is a mora
+            (3, 4, AccentPattern::Nakadaka, "LHHLL"),
+            // 橋(が)
+            (2, 2, AccentPattern::Odaka, "LHL"),
+            // 木(が), a one-mora word
+            (1, 1, AccentPattern::Atamadaka, "HL"),
+            (0, 1, AccentPattern::Heiban, "LH"),
+        ];
+        for (nucleus, morae, pattern, expected) in cases {
+            let accent = PitchAccent { nucleus, morae };
+            assert_eq!(accent.pattern(), pattern, "{nucleus}/{morae}");
+            assert_eq!(contour(nucleus, morae), expected, "{nucleus}/{morae}");
+        }
+    }
+
+    #[test]
+    fn only_dictionary_forms_match() {
+        let dictionary = AccentDictionary::parse(DICTIONARY).unwrap();
+        let taberu = token("食べる", "動詞,自立", "一段", "基本形", "食べる", "タベル");
+        let tabe = token("食べ", "動詞,自立", "一段", "連用形", "食べる", "タベ");
+        assert_eq!(
+            dictionary.token_accent(&taberu),
+            Some(PitchAccent { nucleus: 2, morae: 3 })
+        );
+        assert_eq!(dictionary.token_accent(&tabe), None);
+    }
+}
@@ -0,0 +1,166 @@
+use std::sync::{Arc, OnceLock};
+
+use lindera::tokenizer::Tokenizer;
+
+use crate::accent::AccentDictionary;
+use crate::config::{AnalyzerConfig, SegmentationMode};
+use crate::error::AnalysisError;
+use crate::jlpt::JlptLists;
+use crate::jmdict::Jmdict;
+use crate::kanji::KanjiDictionary;
+use crate::tokenizer::{
+    AnalyzedSentence, analyze_normalized_sentence, create_tokenizer_with_config,
+    create_tokenizer_with_mode,
+};
+
+/// The process-wide analyzer, or the message of the error that kept it from loading
+static SHARED: OnceLock<Result<Analyzer, String>> = OnceLock::new();
+
+/// Cloneable, thread-safe handle to a loaded tokenizer
+///
+/// Loading the embedded dictionary is expensive, so an analyzer should be
+/// built once and shared rather than created per request. Clones are cheap
+/// and refer to the same tokenizer.
+///
+/// The tokenizer for the mode that is not the configured default is only
+/// built the first time it is asked for, since it holds its own copy of
+/// the dictionary.
+///
+/// When an accent dictionary, JLPT lists or JMdict are configured, analyzed
+/// tokens carry their pitch accent, level and glosses. A configured KANJIDIC2
+/// is loaded alongside them for kanji lookups.
+#[derive(Clone)]
+pub struct Analyzer {
+    config: Arc<AnalyzerConfig>,
+    tokenizer: Arc<Tokenizer>,
+    alternate: Arc<OnceLock<Tokenizer>>,
+    accents: Option<Arc<AccentDictionary>>,
+    jlpt: Option<Arc<JlptLists>>,
+    jmdict: Option<Arc<Jmdict>>,
+    kanjidic: Option<Arc<KanjiDictionary>>,
+}
+
+impl Analyzer {
+    /// Load the default dictionary and build a new analyzer
+    pub fn new() -> Result<Self, AnalysisError> {
+        Self::with_config(AnalyzerConfig::default())
+    }
+
+    /// Build a new analyzer from `config`
+    pub fn with_config(config: AnalyzerConfig) -> Result<Self, AnalysisError> {
+        let tokenizer = create_tokenizer_with_config(&config)?;
+        let accents = match &config.accent_dictionary {
+            Some(path) => Some(Arc::new(AccentDictionary::load(path)?)),
+            None => None,
+        };
+        let jlpt = if config.jlpt_vocabulary.is_some() || config.jlpt_kanji.is_some() {
+            Some(Arc::new(JlptLists::load(
+                config.jlpt_vocabulary.as_deref(),
+                config.jlpt_kanji.as_deref(),
+            )?))
+        } else {
+            None
+        };
+        let jmdict = match &config.jmdict {
+            Some(path) => Some(Arc::new(Jmdict::load(path)?)),
+            None => None,
+        };
+        let kanjidic = match &config.kanjidic {
+            Some(path) => Some(Arc::new(KanjiDictionary::load(path)?)),
+            None => None,
+        };
+        Ok(Self {
+            config: Arc::new(config),
+            tokenizer: Arc::new(tokenizer),
+            alternate: Arc::new(OnceLock::new()),
+            accents,
+            jlpt,
+            jmdict,
+            kanjidic,
+        })
+    }
+
+    /// Get the process-wide analyzer, loading it on first use
+    ///
+    /// The analyzer is configured from the environment, see
+    /// [`AnalyzerConfig::from_env`]. It is loaded once: concurrent first
+    /// calls wait for the same load, and a failure is kept and returned to
+    /// every later call rather than retried.
+    pub fn shared() -> Result<Self, AnalysisError> {
+        SHARED
+            .get_or_init(|| {
+                AnalyzerConfig::from_env()
+                    .and_then(Self::with_config)
+                    .map_err(|e| e.to_string())
+            })
+            .clone()
+            .map_err(AnalysisError::SharedAnalyzer)
+    }
+
+    /// The configuration this analyzer was built from
+    pub fn config(&self) -> &AnalyzerConfig {
+        &self.config
+    }
+
+    /// The underlying lindera tokenizer for the configured mode
+    pub fn tokenizer(&self) -> &Tokenizer {
+        &self.tokenizer
+    }
+
+    /// The lindera tokenizer for `mode`
+    pub fn tokenizer_for(&self, mode: SegmentationMode) -> &Tokenizer {
+        if mode == self.config.mode {
+            &self.tokenizer
+        } else {
+            self.alternate
+                .get_or_init(|| create_tokenizer_with_mode(&self.tokenizer, mode))
+        }
+    }
+
+    /// The loaded pitch accent dictionary, if one is configured
+    pub fn accents(&self) -> Option<&AccentDictionary> {
+        self.accents.as_deref()
+    }
+
+    /// The loaded JLPT lists, if any are configured
+    pub fn jlpt(&self) -> Option<&JlptLists> {
+        self.jlpt.as_deref()
+    }
+
+    /// The loaded JMdict, if one is configured
+    pub fn jmdict(&self) -> Option<&Jmdict> {
+        self.jmdict.as_deref()
+    }
+
+    /// The loaded kanji dictionary, if one is configured
+    pub fn kanjidic(&self) -> Option<&KanjiDictionary> {
+        self.kanjidic.as_deref()
+    }
+
+    /// Analyze a Japanese sentence into tokens with grammar details
+    ///
+    /// The text is normalized first as configured, see [`crate::Normalization`].
+    pub fn analyze(&self, text: &str) -> Result<AnalyzedSentence, AnalysisError> {
+        self.analyze_with_mode(text, self.config.mode)
+    }
+
+    /// Analyze a Japanese sentence, segmenting compounds according to `mode`
+    pub fn analyze_with_mode(
+        &self,
+        text: &str,
+        mode: SegmentationMode,
+    ) -> Result<AnalyzedSentence, AnalysisError> {
+        let mut sentence =
+            analyze_normalized_sentence(self.tokenizer_for(mode), text, self.config.normalization)?;
+        if let Some(accents) = &self.accents {
+            accents.annotate(&mut sentence);
+        }
+        if let Some(jlpt) = &self.jlpt {
+            jlpt.annotate(&mut sentence);
+        }
+        if let Some(jmdict) = &self.jmdict {
+            jmdict.annotate(&mut sentence);
+        }
+        Ok(sentence)
+    }
+}
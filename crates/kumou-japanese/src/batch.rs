@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+#[cfg(feature = "tokenizer")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "tokenizer")]
+use crate::analyzer::Analyzer;
+#[cfg(feature = "tokenizer")]
+use crate::config::SegmentationMode;
+#[cfg(feature = "tokenizer")]
+use crate::dialogue::Dialogue;
+#[cfg(feature = "tokenizer")]
+use crate::error::AnalysisError;
+#[cfg(feature = "tokenizer")]
+use crate::sentence::split_sentences;
+use crate::tokenizer::AnalyzedSentence;
+
+/// Helper threads running for [`Analyzer::analyze_parallel`], across all callers
+#[cfg(feature = "tokenizer")]
+static HELPERS: AtomicUsize = AtomicUsize::new(0);
+
+/// Analyses of every utterance of a dialogue
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DialogueAnalysis {
+    pub dialogue_id: u32,
+    /// One analysis per sentence, as split by [`crate::split_sentences`],
+    /// keyed by turn number
+    pub utterances: BTreeMap<u32, Vec<AnalyzedSentence>>,
+}
+
+impl DialogueAnalysis {
+    /// Analysis of the given sentence of an utterance
+    pub fn sentence(&self, turn_num: u32, sentence: usize) -> Option<&AnalyzedSentence> {
+        self.utterances.get(&turn_num)?.get(sentence)
+    }
+}
+
+#[cfg(feature = "tokenizer")]
+impl Analyzer {
+    /// Analyze every sentence of a dialogue in parallel
+    pub fn analyze_dialogue(&self, dialogue: &Dialogue) -> Result<DialogueAnalysis, AnalysisError> {
+        let mut analyses = self.analyze_dialogues(std::slice::from_ref(dialogue))?;
+        Ok(analyses.remove(&dialogue.dialogue_id).unwrap_or_default())
+    }
+
+    /// Analyze every sentence of `dialogues` in parallel, keyed by dialogue id
+    pub fn analyze_dialogues(
+        &self,
+        dialogues: &[Dialogue],
+    ) -> Result<BTreeMap<u32, DialogueAnalysis>, AnalysisError> {
+        self.analyze_dialogues_with_mode(dialogues, self.config().mode)
+    }
+
+    /// Analyze every sentence of `dialogues` in parallel, segmenting
+    /// compounds according to `mode`
+    ///
+    /// Sentences are spread over threads as described in
+    /// [`Self::analyze_parallel`]. If any sentence fails, the first error in
+    /// sentence order is returned.
+    pub fn analyze_dialogues_with_mode(
+        &self,
+        dialogues: &[Dialogue],
+        mode: SegmentationMode,
+    ) -> Result<BTreeMap<u32, DialogueAnalysis>, AnalysisError> {
+        // Dialogue id, turn number and text of every sentence
+        let sentences: Vec<(u32, u32, String)> = dialogues
+            .iter()
+            .flat_map(|dialogue| {
+                dialogue.utterances.iter().flat_map(move |utterance| {
+                    split_sentences(&utterance.utterance)
+                        .into_iter()
+                        .map(move |s| (dialogue.dialogue_id, utterance.turn_num, s.text))
+                })
+            })
+            .collect();
+        let texts: Vec<&str> = sentences.iter().map(|(_, _, text)| text.as_str()).collect();
+        let analyzed = self.analyze_parallel(&texts, mode)?;
+
+        let mut analyses: BTreeMap<u32, DialogueAnalysis> = dialogues
+            .iter()
+            .map(|d| {
+                let analysis = DialogueAnalysis {
+                    dialogue_id: d.dialogue_id,
+                    utterances: d.utterances.iter().map(|u| (u.turn_num, Vec::new())).collect(),
+                };
+                (d.dialogue_id, analysis)
+            })
+            .collect();
+        for ((dialogue_id, turn_num, _), sentence) in sentences.into_iter().zip(analyzed) {
+            if let Some(analysis) = analyses.get_mut(&dialogue_id) {
+                analysis.utterances.entry(turn_num).or_default().push(sentence);
+            }
+        }
+        Ok(analyses)
+    }
+
+    /// Analyze `texts` on scoped threads, keeping their order
+    ///
+    /// The calling thread takes a share of the texts itself. Helper threads
+    /// are capped process-wide at one less than the number of cores, so
+    /// concurrent calls share the cores instead of each starting a thread per
+    /// core; a call that finds no helper free analyzes everything itself.
+    pub fn analyze_parallel(
+        &self,
+        texts: &[&str],
+        mode: SegmentationMode,
+    ) -> Result<Vec<AnalyzedSentence>, AnalysisError> {
+        // Build the tokenizer for `mode` up front rather than on every thread
+        self.tokenizer_for(mode);
+        let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+        map_parallel(texts, &HELPERS, cores, |text| self.analyze_with_mode(text, mode))
+    }
+}
+
+/// Apply `f` to every item on scoped threads, keeping the items' order
+///
+/// Helpers are reserved from `helpers`, which stays below `cores`; the
+/// calling thread handles the first chunk. If `f` fails, the first error in
+/// item order is returned.
+#[cfg(feature = "tokenizer")]
+fn map_parallel<T, R, F>(
+    items: &[T],
+    helpers: &'static AtomicUsize,
+    cores: usize,
+    f: F,
+) -> Result<Vec<R>, AnalysisError>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Result<R, AnalysisError> + Sync,
+{
+    let wanted = items.len().min(cores).saturating_sub(1);
+    let reservation = HelperReservation::reserve(helpers, wanted, cores.saturating_sub(1));
+    let chunk_size = items.len().div_ceil(reservation.count + 1).max(1);
+
+    let map = |chunk: &[T]| chunk.iter().map(&f).collect::<Result<Vec<_>, _>>();
+    let mut chunks = items.chunks(chunk_size);
+    let own = chunks.next().unwrap_or_default();
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = chunks.map(|chunk| scope.spawn(move || map(chunk))).collect();
+
+        let mut mapped = map(own)?;
+        mapped.reserve(items.len() - mapped.len());
+        for worker in workers {
+            let chunk = worker
+                .join()
+                .map_err(|_| AnalysisError::Tokenization("analysis thread panicked".into()))?;
+            mapped.extend(chunk?);
+        }
+        Ok(mapped)
+    })
+}
+
+/// Helper threads taken from a counter such as [`HELPERS`], given back when
+/// dropped
+#[cfg(feature = "tokenizer")]
+struct HelperReservation {
+    helpers: &'static AtomicUsize,
+    count: usize,
+}
+
+#[cfg(feature = "tokenizer")]
+impl HelperReservation {
+    /// Reserve up to `wanted` helpers while keeping the total at most `limit`
+    fn reserve(helpers: &'static AtomicUsize, wanted: usize, limit: usize) -> Self {
+        let mut count = 0;
+        let _ = helpers.fetch_update(Ordering::AcqRel, Ordering::Acquire, |running| {
+            count = wanted.min(limit.saturating_sub(running));
+            Some(running + count)
+        });
+        Self { helpers, count }
+    }
+}
+
+#[cfg(feature = "tokenizer")]
+impl Drop for HelperReservation {
+    fn drop(&mut self) {
+        self.helpers.fetch_sub(self.count, Ordering::AcqRel);
+    }
+}
+
+#[cfg(all(test, feature = "tokenizer"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reservations_stay_within_the_limit() {
+        static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+        let first = HelperReservation::reserve(&RUNNING, 2, 3);
+        assert_eq!(first.count, 2);
+        // Only one helper is left for the second caller
+        let second = HelperReservation::reserve(&RUNNING, 4, 3);
+        assert_eq!(second.count, 1);
+        let third = HelperReservation::reserve(&RUNNING, 4, 3);
+        assert_eq!(third.count, 0);
+        assert_eq!(RUNNING.load(Ordering::Acquire), 3);
+
+        drop(first);
+        assert_eq!(RUNNING.load(Ordering::Acquire), 1);
+        let fourth = HelperReservation::reserve(&RUNNING, 4, 3);
+        assert_eq!(fourth.count, 2);
+        drop((second, third, fourth));
+        assert_eq!(RUNNING.load(Ordering::Acquire), 0);
+    }
+
+    #[test]
+    fn results_keep_item_order() {
+        static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+        let items: Vec<usize> = (0..100).collect();
+        for cores in [1, 2, 3, 8] {
+            let doubled = map_parallel(&items, &RUNNING, cores, |&i| Ok(i * 2)).unwrap();
+            assert_eq!(doubled, items.iter().map(|i| i * 2).collect::<Vec<_>>(), "{cores}");
+            assert_eq!(RUNNING.load(Ordering::Acquire), 0);
+        }
+        assert!(map_parallel(&[] as &[usize], &RUNNING, 4, |&i| Ok(i)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn first_error_in_item_order() {
+        static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+        let items: Vec<usize> = (0..40).collect();
+        let result = map_parallel(&items, &RUNNING, 4, |&i| {
+            if i % 15 == 14 {
+                Err(AnalysisError::Tokenization(i.to_string()))
+            } else {
+                Ok(i)
+            }
+        });
+        assert!(matches!(result, Err(AnalysisError::Tokenization(item)) if item == "14"));
+        assert_eq!(RUNNING.load(Ordering::Acquire), 0);
+    }
+
+    #[test]
+    fn busy_helpers_leave_the_work_to_the_caller() {
+        static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+        let busy = HelperReservation::reserve(&RUNNING, 3, 3);
+        let caller = std::thread::current().id();
+        let threads = map_parallel(&[1, 2, 3, 4], &RUNNING, 4, |_| Ok(std::thread::current().id()))
+            .unwrap();
+        assert!(threads.iter().all(|&id| id == caller));
+        drop(busy);
+    }
+}
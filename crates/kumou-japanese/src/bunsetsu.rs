@@ -0,0 +1,284 @@
+use serde::{Deserialize, Serialize};
+
+use crate::pos::{PosMajor, PosSub};
+use crate::tokenizer::{AnalyzedSentence, AnalyzedToken};
+
+/// A bunsetsu (文節): content word(s) followed by their function words
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Bunsetsu {
+    /// Index of the first token in the sentence
+    pub start: usize,
+    /// Index one past the last token
+    pub end: usize,
+    /// Index of the last content word (主辞)
+    pub head: usize,
+    /// Index of the last function word (機能語), if any
+    pub function: Option<usize>,
+    /// Concatenated surface of the tokens
+    pub surface: String,
+}
+
+impl Bunsetsu {
+    /// The tokens of this bunsetsu within `sentence`
+    pub fn tokens<'a>(&self, sentence: &'a AnalyzedSentence) -> &'a [AnalyzedToken] {
+        &sentence.tokens[self.start..self.end]
+    }
+}
+
+/// Group a sentence's tokens into bunsetsu
+///
+/// A new bunsetsu starts at each content word, except that compound nouns,
+/// nominal suffixes, prefixes and サ変 nouns followed by する stay together.
+/// Function words and closing punctuation attach to the bunsetsu before.
+pub fn chunk_bunsetsu(sentence: &AnalyzedSentence) -> Vec<Bunsetsu> {
+    chunk_tokens(&sentence.tokens)
+}
+
+pub(crate) fn chunk_tokens(tokens: &[AnalyzedToken]) -> Vec<Bunsetsu> {
+    let mut chunks: Vec<Bunsetsu> = Vec::new();
+    let mut start = 0;
+
+    for i in 1..=tokens.len() {
+        let boundary = match tokens.get(i) {
+            Some(token) => {
+                let chunk = &tokens[start..i];
+                starts_bunsetsu(chunk, token)
+            }
+            None => true,
+        };
+        if boundary {
+            chunks.push(make_bunsetsu(tokens, start, i));
+            start = i;
+        }
+    }
+
+    chunks
+}
+
+fn make_bunsetsu(tokens: &[AnalyzedToken], start: usize, end: usize) -> Bunsetsu {
+    let range = start..end;
+    let head = range
+        .clone()
+        .rev()
+        .find(|&i| {
+            is_content(&tokens[i])
+                && !(i > start && is_dependent_after_te(&tokens[i - 1], &tokens[i]))
+        })
+        .unwrap_or(start);
+    let function = range
+        .clone()
+        .rev()
+        .find(|&i| is_function(&tokens[i]) && !tokens[i].pos.major.is_symbol());
+
+    Bunsetsu {
+        start,
+        end,
+        head,
+        function,
+        surface: tokens[range].iter().map(|t| t.surface.as_str()).collect(),
+    }
+}
+
+/// Whether `token` opens a new bunsetsu after the tokens of `chunk`
+fn starts_bunsetsu(chunk: &[AnalyzedToken], token: &AnalyzedToken) -> bool {
+    let Some(previous) = chunk.last() else {
+        return false;
+    };
+    let major = &token.pos.major;
+    let sub1 = &token.pos.sub1;
+
+    if major.is_symbol() {
+        return *sub1 == PosSub::OpeningBracket;
+    }
+    if is_function(token) || is_dependent_after_te(previous, token) {
+        return false;
+    }
+    let after_opening =
+        previous.pos.major.is_symbol() && previous.pos.sub1 == PosSub::OpeningBracket;
+    if previous.pos.major.is_prefix() || after_opening {
+        return false;
+    }
+
+    let has_function = chunk.iter().any(is_function);
+    if is_suffix(token) {
+        return has_function;
+    }
+    // Compound nouns: 東京 国際 空港
+    if *major == PosMajor::Noun
+        && *sub1 != PosSub::Dependent
+        && previous.pos.major == PosMajor::Noun
+    {
+        return has_function;
+    }
+    // サ変 nouns with する: 勉強 する
+    if *major == PosMajor::Verb
+        && token.base_form == "する"
+        && (previous.pos.sub1.takes_suru() || previous.pos.sub2.takes_suru())
+    {
+        return has_function;
+    }
+    true
+}
+
+/// Particles, auxiliaries, dependent verbs/adjectives and non-opening symbols
+fn is_function(token: &AnalyzedToken) -> bool {
+    match token.pos.major {
+        PosMajor::Particle | PosMajor::AuxiliaryVerb => true,
+        PosMajor::Verb | PosMajor::Adjective => {
+            matches!(token.pos.sub1, PosSub::Dependent | PosSub::Suffix)
+        }
+        _ if token.pos.major.is_symbol() => token.pos.sub1 != PosSub::OpeningBracket,
+        _ => false,
+    }
+}
+
+/// UniDic's いる of ～ている is only possibly dependent (非自立可能), so it
+/// is told apart from the independent いる by the て before it
+fn is_dependent_after_te(previous: &AnalyzedToken, token: &AnalyzedToken) -> bool {
+    token.pos.major == PosMajor::Verb
+        && token.pos.sub1 == PosSub::PossiblyDependent
+        && previous.pos.major == PosMajor::Particle
+        && matches!(previous.surface.as_str(), "て" | "で")
+}
+
+/// Nominal suffixes: IPADIC 名詞,接尾 and UniDic 接尾辞
+fn is_suffix(token: &AnalyzedToken) -> bool {
+    token.pos.major == PosMajor::Suffix
+        || (token.pos.major == PosMajor::Noun && token.pos.sub1 == PosSub::Suffix)
+}
+
+/// Content words that can head a bunsetsu; nominal suffixes such as さん
+/// defer to the noun they attach to
+fn is_content(token: &AnalyzedToken) -> bool {
+    !is_function(token) && !token.pos.major.is_symbol() && !is_suffix(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sentence, token};
+
+    /// Token whose base form is its surface
+    fn t(surface: &str, pos: &str) -> AnalyzedToken {
+        token(surface, pos, "*", "*", surface, "*")
+    }
+
+    /// する in the given form
+    fn suru(surface: &str, pos: &str) -> AnalyzedToken {
+        token(surface, pos, "サ変・スル", "連用形", "する", "*")
+    }
+
+    fn surfaces(tokens: Vec<AnalyzedToken>) -> Vec<String> {
+        chunk_bunsetsu(&sentence(tokens))
+            .into_iter()
+            .map(|b| b.surface)
+            .collect()
+    }
+
+    #[test]
+    fn ipadic_compounds_and_function_words() {
+        let tokens = vec![
+            t("東京", "名詞,固有名詞"),
+            t("国際", "名詞,一般"),
+            t("空港", "名詞,一般"),
+            t("に", "助詞,格助詞"),
+            t("行き", "動詞,自立"),
+            t("ます", "助動詞"),
+            t("。", "記号,句点"),
+        ];
+        assert_eq!(surfaces(tokens), ["東京国際空港に", "行きます。"]);
+    }
+
+    #[test]
+    fn ipadic_prefix_suffix_and_suru() {
+        let tokens = vec![
+            t("お", "接頭詞,名詞接続"),
+            t("茶", "名詞,一般"),
+            t("を", "助詞,格助詞"),
+            t("田中", "名詞,固有名詞"),
+            t("さん", "名詞,接尾"),
+            t("が", "助詞,格助詞"),
+            t("勉強", "名詞,サ変接続"),
+            suru("し", "動詞,自立"),
+            t("て", "助詞,接続助詞"),
+            t("いる", "動詞,非自立"),
+        ];
+        let chunks = chunk_bunsetsu(&sentence(tokens));
+        let surfaces: Vec<&str> = chunks.iter().map(|b| b.surface.as_str()).collect();
+        assert_eq!(surfaces, ["お茶を", "田中さんが", "勉強している"]);
+        // さん defers to 田中, いる is a function word
+        assert_eq!((chunks[1].head, chunks[1].function), (3, Some(5)));
+        assert_eq!((chunks[2].head, chunks[2].function), (7, Some(9)));
+    }
+
+    #[test]
+    fn ipadic_brackets() {
+        let tokens = vec![
+            t("「", "記号,括弧開"),
+            t("はい", "感動詞"),
+            t("」", "記号,括弧閉"),
+            t("と", "助詞,格助詞"),
+            t("言っ", "動詞,自立"),
+            t("た", "助動詞"),
+        ];
+        assert_eq!(surfaces(tokens), ["「はい」と", "言った"]);
+    }
+
+    #[test]
+    fn unidic_compounds_and_function_words() {
+        let tokens = vec![
+            t("東京", "名詞,固有名詞"),
+            t("国際", "名詞,普通名詞"),
+            t("空港", "名詞,普通名詞"),
+            t("に", "助詞,格助詞"),
+            t("行き", "動詞,非自立可能"),
+            t("ます", "助動詞"),
+            t("。", "補助記号,句点"),
+        ];
+        assert_eq!(surfaces(tokens), ["東京国際空港に", "行きます。"]);
+    }
+
+    #[test]
+    fn unidic_prefix_suffix_and_suru() {
+        let tokens = vec![
+            t("お", "接頭辞"),
+            t("茶", "名詞,普通名詞"),
+            t("を", "助詞,格助詞"),
+            t("田中", "名詞,固有名詞"),
+            t("さん", "接尾辞,名詞的"),
+            t("が", "助詞,格助詞"),
+            t("勉強", "名詞,普通名詞,サ変可能"),
+            suru("し", "動詞,非自立可能"),
+            t("て", "助詞,接続助詞"),
+            t("いる", "動詞,非自立可能"),
+        ];
+        let chunks = chunk_bunsetsu(&sentence(tokens));
+        let surfaces: Vec<&str> = chunks.iter().map(|b| b.surface.as_str()).collect();
+        assert_eq!(surfaces, ["お茶を", "田中さんが", "勉強している"]);
+        assert_eq!(chunks[1].head, 3);
+        assert_eq!(chunks[2].head, 7);
+    }
+
+    #[test]
+    fn unidic_brackets() {
+        let tokens = vec![
+            t("「", "補助記号,括弧開"),
+            t("はい", "感動詞,一般"),
+            t("」", "補助記号,括弧閉"),
+            t("と", "助詞,格助詞"),
+            t("言っ", "動詞,一般"),
+            t("た", "助動詞"),
+        ];
+        assert_eq!(surfaces(tokens), ["「はい」と", "言った"]);
+    }
+
+    #[test]
+    fn independent_iru_starts_a_bunsetsu() {
+        let tokens = vec![
+            t("家", "名詞,普通名詞"),
+            t("に", "助詞,格助詞"),
+            t("いる", "動詞,非自立可能"),
+        ];
+        assert_eq!(surfaces(tokens), ["家に", "いる"]);
+    }
+}
@@ -0,0 +1,175 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AnalysisError;
+use crate::normalize::Normalization;
+
+/// Environment variable selecting the dictionary for [`AnalyzerConfig::from_env`]
+pub const DICTIONARY_ENV: &str = "KUMOU_DICTIONARY";
+/// Environment variable selecting the default mode for [`AnalyzerConfig::from_env`]
+pub const MODE_ENV: &str = "KUMOU_MODE";
+/// Environment variable selecting the text normalization for [`AnalyzerConfig::from_env`]
+pub const NORMALIZATION_ENV: &str = "KUMOU_NORMALIZATION";
+/// Environment variable pointing at a user dictionary CSV for [`AnalyzerConfig::from_env`]
+pub const USER_DICTIONARY_ENV: &str = "KUMOU_USER_DICTIONARY";
+/// Environment variable pointing at a pitch accent CSV for [`AnalyzerConfig::from_env`]
+pub const ACCENT_DICTIONARY_ENV: &str = "KUMOU_ACCENT_DICTIONARY";
+/// Environment variable pointing at a JLPT vocabulary CSV for [`AnalyzerConfig::from_env`]
+pub const JLPT_VOCABULARY_ENV: &str = "KUMOU_JLPT_VOCABULARY";
+/// Environment variable pointing at a JLPT kanji CSV for [`AnalyzerConfig::from_env`]
+pub const JLPT_KANJI_ENV: &str = "KUMOU_JLPT_KANJI";
+/// Environment variable pointing at a JMdict XML file for [`AnalyzerConfig::from_env`]
+pub const JMDICT_ENV: &str = "KUMOU_JMDICT";
+/// Environment variable pointing at a KANJIDIC2 XML file for [`AnalyzerConfig::from_env`]
+pub const KANJIDIC_ENV: &str = "KUMOU_KANJIDIC";
+
+/// Dictionary used to segment and tag text
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum DictionaryBackend {
+    /// IPADIC, embedded with the `tokenizer` feature
+    #[default]
+    Ipadic,
+    /// IPADIC-NEologd, embedded with the `embed-ipadic-neologd` feature
+    IpadicNeologd,
+    /// UniDic, embedded with the `embed-unidic` feature
+    UniDic,
+}
+
+impl DictionaryBackend {
+    /// Name used in configuration and by lindera, e.g. `ipadic`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DictionaryBackend::Ipadic => "ipadic",
+            DictionaryBackend::IpadicNeologd => "ipadic-neologd",
+            DictionaryBackend::UniDic => "unidic",
+        }
+    }
+
+    /// Cargo feature that embeds this dictionary
+    pub fn feature(&self) -> &'static str {
+        match self {
+            DictionaryBackend::Ipadic => "tokenizer",
+            DictionaryBackend::IpadicNeologd => "embed-ipadic-neologd",
+            DictionaryBackend::UniDic => "embed-unidic",
+        }
+    }
+
+    /// Whether this dictionary is compiled into the current build
+    pub fn is_available(&self) -> bool {
+        match self {
+            DictionaryBackend::Ipadic => cfg!(feature = "tokenizer"),
+            DictionaryBackend::IpadicNeologd => cfg!(feature = "embed-ipadic-neologd"),
+            DictionaryBackend::UniDic => cfg!(feature = "embed-unidic"),
+        }
+    }
+}
+
+impl FromStr for DictionaryBackend {
+    type Err = AnalysisError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "ipadic" => Ok(DictionaryBackend::Ipadic),
+            "ipadic-neologd" | "neologd" => Ok(DictionaryBackend::IpadicNeologd),
+            "unidic" => Ok(DictionaryBackend::UniDic),
+            _ => Err(AnalysisError::UnknownDictionary(s.to_string())),
+        }
+    }
+}
+
+/// How eagerly compounds are split into morphemes
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum SegmentationMode {
+    /// Keep dictionary compounds whole, e.g. 東京国際空港
+    #[default]
+    Normal,
+    /// Split long compounds into parts, e.g. 東京 / 国際 / 空港
+    Decompose,
+}
+
+impl SegmentationMode {
+    /// Name used in configuration, e.g. `normal`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SegmentationMode::Normal => "normal",
+            SegmentationMode::Decompose => "decompose",
+        }
+    }
+}
+
+impl FromStr for SegmentationMode {
+    type Err = AnalysisError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "normal" => Ok(SegmentationMode::Normal),
+            "decompose" => Ok(SegmentationMode::Decompose),
+            _ => Err(AnalysisError::UnknownMode(s.to_string())),
+        }
+    }
+}
+
+/// Settings used to build an analyzer
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AnalyzerConfig {
+    /// Dictionary to load
+    pub dictionary: DictionaryBackend,
+    /// Segmentation mode used when none is given per call
+    pub mode: SegmentationMode,
+    /// Unicode normalization applied before tokenizing
+    pub normalization: Normalization,
+    /// User dictionary CSV to load, see [`crate::UserDictionary`]
+    pub user_dictionary: Option<PathBuf>,
+    /// Pitch accent CSV to annotate tokens from, see [`crate::AccentDictionary`]
+    pub accent_dictionary: Option<PathBuf>,
+    /// JLPT vocabulary CSV, see [`crate::JlptLists`]
+    pub jlpt_vocabulary: Option<PathBuf>,
+    /// JLPT kanji CSV, see [`crate::JlptLists`]
+    pub jlpt_kanji: Option<PathBuf>,
+    /// JMdict XML to look up token glosses in, see [`crate::Jmdict`]
+    pub jmdict: Option<PathBuf>,
+    /// KANJIDIC2 XML to look up kanji in, see [`crate::KanjiDictionary`]
+    pub kanjidic: Option<PathBuf>,
+}
+
+impl AnalyzerConfig {
+    /// Read the configuration from `KUMOU_*` environment variables
+    ///
+    /// Unset variables keep their default values.
+    pub fn from_env() -> Result<Self, AnalysisError> {
+        let mut config = Self::default();
+        if let Ok(dictionary) = std::env::var(DICTIONARY_ENV) {
+            config.dictionary = dictionary.parse()?;
+        }
+        if let Ok(mode) = std::env::var(MODE_ENV) {
+            config.mode = mode.parse()?;
+        }
+        if let Ok(normalization) = std::env::var(NORMALIZATION_ENV) {
+            config.normalization = normalization.parse()?;
+        }
+        if let Some(path) = std::env::var_os(USER_DICTIONARY_ENV) {
+            config.user_dictionary = Some(PathBuf::from(path));
+        }
+        if let Some(path) = std::env::var_os(ACCENT_DICTIONARY_ENV) {
+            config.accent_dictionary = Some(PathBuf::from(path));
+        }
+        if let Some(path) = std::env::var_os(JLPT_VOCABULARY_ENV) {
+            config.jlpt_vocabulary = Some(PathBuf::from(path));
+        }
+        if let Some(path) = std::env::var_os(JLPT_KANJI_ENV) {
+            config.jlpt_kanji = Some(PathBuf::from(path));
+        }
+        if let Some(path) = std::env::var_os(JMDICT_ENV) {
+            config.jmdict = Some(PathBuf::from(path));
+        }
+        if let Some(path) = std::env::var_os(KANJIDIC_ENV) {
+            config.kanjidic = Some(PathBuf::from(path));
+        }
+        Ok(config)
+    }
+}
@@ -0,0 +1,603 @@
+use serde::{Deserialize, Serialize};
+
+use crate::pos::{ConjugationType, PosMajor, PosSub};
+use crate::tokenizer::{AnalyzedToken, PartOfSpeech};
+
+/// Inflection class of a verb or adjective lemma
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ConjugationClass {
+    /// 五段 verbs, e.g. 書く, 買う
+    Godan(GodanVariant),
+    /// 一段 verbs, e.g. 食べる, 見る
+    Ichidan,
+    /// 一段 verbs with the short imperative くれ
+    IchidanKureru,
+    /// カ変: 来る
+    Kuru,
+    /// サ変: する and its compounds
+    Suru,
+    /// サ変 verbs of one kanji and する, e.g. 愛する, 察する
+    SuruSuffix,
+    /// サ変 verbs in ずる, e.g. 信ずる
+    Zuru,
+    /// い-adjectives, e.g. 高い
+    IAdjective,
+    /// The irregular adjective いい (よい)
+    IAdjectiveIi,
+    /// な-adjectives, e.g. 静か
+    NaAdjective,
+}
+
+/// Godan verbs whose forms deviate from the regular pattern
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum GodanVariant {
+    Regular,
+    /// 行く: 行って, 行った
+    Iku,
+    /// ある: negative ない
+    Aru,
+    /// いらっしゃる, なさる, くださる: いらっしゃいます, いらっしゃい
+    Honorific,
+    /// 問う, 請う: 問うて, 問うた
+    UOnbin,
+}
+
+impl ConjugationClass {
+    /// English label, e.g. "Godan verb"
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConjugationClass::Godan(_) => "Godan verb",
+            ConjugationClass::Ichidan | ConjugationClass::IchidanKureru => "Ichidan verb",
+            ConjugationClass::Kuru => "Irregular verb (kuru)",
+            ConjugationClass::Suru | ConjugationClass::SuruSuffix | ConjugationClass::Zuru => {
+                "Irregular verb (suru)"
+            }
+            ConjugationClass::IAdjective | ConjugationClass::IAdjectiveIi => "i-Adjective",
+            ConjugationClass::NaAdjective => "na-Adjective",
+        }
+    }
+
+    /// Determine the class from IPADIC's conjugation type (活用型) and POS
+    ///
+    /// な-adjectives have no conjugation type in IPADIC and are recognized
+    /// from the 形容動詞語幹 POS subcategory instead.
+    pub fn from_ipadic(conjugation_type: &ConjugationType, pos: &PartOfSpeech) -> Option<Self> {
+        if pos.major == PosMajor::Noun && pos.sub1 == PosSub::NaAdjectiveStem {
+            return Some(ConjugationClass::NaAdjective);
+        }
+        if !matches!(pos.major, PosMajor::Verb | PosMajor::Adjective) {
+            return None;
+        }
+
+        let class = match conjugation_type {
+            ConjugationType::GodanKaSokuonbin | ConjugationType::GodanKaSokuonbinYuku => {
+                ConjugationClass::Godan(GodanVariant::Iku)
+            }
+            ConjugationType::GodanRaAru => ConjugationClass::Godan(GodanVariant::Aru),
+            ConjugationType::GodanRaSpecial => ConjugationClass::Godan(GodanVariant::Honorific),
+            ConjugationType::GodanWaUOnbin => ConjugationClass::Godan(GodanVariant::UOnbin),
+            t if t.is_godan() => ConjugationClass::Godan(GodanVariant::Regular),
+            ConjugationType::IchidanKureru => ConjugationClass::IchidanKureru,
+            t if t.is_ichidan() => ConjugationClass::Ichidan,
+            t if t.is_kahen() => ConjugationClass::Kuru,
+            ConjugationType::SahenSuffixSuru => ConjugationClass::SuruSuffix,
+            ConjugationType::SahenZuru => ConjugationClass::Zuru,
+            t if t.is_sahen() => ConjugationClass::Suru,
+            ConjugationType::AdjectiveIi => ConjugationClass::IAdjectiveIi,
+            t if t.is_adjective() => ConjugationClass::IAdjective,
+            _ => return None,
+        };
+        Some(class)
+    }
+}
+
+/// A form in a conjugation paradigm
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ParadigmForm {
+    Dictionary,
+    Masu,
+    Te,
+    Ta,
+    Nai,
+    Potential,
+    Passive,
+    Causative,
+    Volitional,
+    Conditional,
+    Imperative,
+}
+
+impl ParadigmForm {
+    pub const ALL: [ParadigmForm; 11] = [
+        ParadigmForm::Dictionary,
+        ParadigmForm::Masu,
+        ParadigmForm::Te,
+        ParadigmForm::Ta,
+        ParadigmForm::Nai,
+        ParadigmForm::Potential,
+        ParadigmForm::Passive,
+        ParadigmForm::Causative,
+        ParadigmForm::Volitional,
+        ParadigmForm::Conditional,
+        ParadigmForm::Imperative,
+    ];
+
+    /// English label
+    pub fn label(&self) -> &'static str {
+        match self {
+            ParadigmForm::Dictionary => "Dictionary",
+            ParadigmForm::Masu => "Polite (masu)",
+            ParadigmForm::Te => "Te-form",
+            ParadigmForm::Ta => "Past (ta)",
+            ParadigmForm::Nai => "Negative (nai)",
+            ParadigmForm::Potential => "Potential",
+            ParadigmForm::Passive => "Passive",
+            ParadigmForm::Causative => "Causative",
+            ParadigmForm::Volitional => "Volitional",
+            ParadigmForm::Conditional => "Conditional (ba)",
+            ParadigmForm::Imperative => "Imperative",
+        }
+    }
+
+    /// Japanese name, e.g. ます形
+    pub fn label_ja(&self) -> &'static str {
+        match self {
+            ParadigmForm::Dictionary => "辞書形",
+            ParadigmForm::Masu => "ます形",
+            ParadigmForm::Te => "て形",
+            ParadigmForm::Ta => "た形",
+            ParadigmForm::Nai => "ない形",
+            ParadigmForm::Potential => "可能形",
+            ParadigmForm::Passive => "受身形",
+            ParadigmForm::Causative => "使役形",
+            ParadigmForm::Volitional => "意向形",
+            ParadigmForm::Conditional => "仮定形",
+            ParadigmForm::Imperative => "命令形",
+        }
+    }
+}
+
+/// One generated form of a lemma
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConjugatedForm {
+    pub form: ParadigmForm,
+    pub text: String,
+}
+
+/// All generated forms of a lemma
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Paradigm {
+    /// Dictionary form the paradigm was generated from
+    pub lemma: String,
+    pub class: ConjugationClass,
+    /// Forms in [`ParadigmForm::ALL`] order; forms the word lacks are omitted
+    pub forms: Vec<ConjugatedForm>,
+}
+
+impl Paradigm {
+    /// Look up a single form
+    pub fn get(&self, form: ParadigmForm) -> Option<&str> {
+        self.forms
+            .iter()
+            .find(|f| f.form == form)
+            .map(|f| f.text.as_str())
+    }
+}
+
+/// Generate the paradigm of a verb or adjective token
+///
+/// Returns `None` for tokens that do not conjugate.
+pub fn token_paradigm(token: &AnalyzedToken) -> Option<Paradigm> {
+    let class = ConjugationClass::from_ipadic(&token.conjugation_type, &token.pos)?;
+    let lemma = if class == ConjugationClass::NaAdjective || token.base_form == "*" {
+        &token.surface
+    } else {
+        &token.base_form
+    };
+    conjugate(lemma, class)
+}
+
+/// Generate the paradigm of `lemma` conjugated as `class`
+///
+/// Returns `None` when the lemma does not end the way its class requires,
+/// e.g. an ichidan verb not ending in る.
+pub fn conjugate(lemma: &str, class: ConjugationClass) -> Option<Paradigm> {
+    let forms: Vec<(ParadigmForm, String)> = match class {
+        ConjugationClass::Godan(variant) => godan_forms(lemma, variant)?,
+        ConjugationClass::Ichidan | ConjugationClass::IchidanKureru => {
+            let stem = lemma.strip_suffix('る')?;
+            let imperative = if class == ConjugationClass::IchidanKureru {
+                stem.to_string()
+            } else {
+                format!("{stem}ろ")
+            };
+            vec![
+                (ParadigmForm::Masu, format!("{stem}ます")),
+                (ParadigmForm::Te, format!("{stem}て")),
+                (ParadigmForm::Ta, format!("{stem}た")),
+                (ParadigmForm::Nai, format!("{stem}ない")),
+                (ParadigmForm::Potential, format!("{stem}られる")),
+                (ParadigmForm::Passive, format!("{stem}られる")),
+                (ParadigmForm::Causative, format!("{stem}させる")),
+                (ParadigmForm::Volitional, format!("{stem}よう")),
+                (ParadigmForm::Conditional, format!("{stem}れば")),
+                (ParadigmForm::Imperative, imperative),
+            ]
+        }
+        ConjugationClass::Kuru => kuru_forms(lemma)?,
+        ConjugationClass::Suru => {
+            let prefix = lemma.strip_suffix("する")?;
+            vec![
+                (ParadigmForm::Masu, format!("{prefix}します")),
+                (ParadigmForm::Te, format!("{prefix}して")),
+                (ParadigmForm::Ta, format!("{prefix}した")),
+                (ParadigmForm::Nai, format!("{prefix}しない")),
+                (ParadigmForm::Potential, format!("{prefix}できる")),
+                (ParadigmForm::Passive, format!("{prefix}される")),
+                (ParadigmForm::Causative, format!("{prefix}させる")),
+                (ParadigmForm::Volitional, format!("{prefix}しよう")),
+                (ParadigmForm::Conditional, format!("{prefix}すれば")),
+                (ParadigmForm::Imperative, format!("{prefix}しろ")),
+            ]
+        }
+        ConjugationClass::SuruSuffix => {
+            // Mostly godan-like in さ and せ: 愛さない, 愛せる, 愛せ
+            let prefix = lemma.strip_suffix("する")?;
+            vec![
+                (ParadigmForm::Masu, format!("{prefix}します")),
+                (ParadigmForm::Te, format!("{prefix}して")),
+                (ParadigmForm::Ta, format!("{prefix}した")),
+                (ParadigmForm::Nai, format!("{prefix}さない")),
+                (ParadigmForm::Potential, format!("{prefix}せる")),
+                (ParadigmForm::Passive, format!("{prefix}される")),
+                (ParadigmForm::Causative, format!("{prefix}させる")),
+                (ParadigmForm::Volitional, format!("{prefix}そう")),
+                (ParadigmForm::Conditional, format!("{prefix}すれば")),
+                (ParadigmForm::Imperative, format!("{prefix}せよ")),
+            ]
+        }
+        ConjugationClass::Zuru => {
+            let prefix = lemma.strip_suffix("ずる")?;
+            vec![
+                (ParadigmForm::Masu, format!("{prefix}じます")),
+                (ParadigmForm::Te, format!("{prefix}じて")),
+                (ParadigmForm::Ta, format!("{prefix}じた")),
+                (ParadigmForm::Nai, format!("{prefix}じない")),
+                (ParadigmForm::Potential, format!("{prefix}じられる")),
+                (ParadigmForm::Passive, format!("{prefix}じられる")),
+                (ParadigmForm::Causative, format!("{prefix}じさせる")),
+                (ParadigmForm::Volitional, format!("{prefix}じよう")),
+                (ParadigmForm::Conditional, format!("{prefix}ずれば")),
+                (ParadigmForm::Imperative, format!("{prefix}じろ")),
+            ]
+        }
+        ConjugationClass::IAdjective | ConjugationClass::IAdjectiveIi => {
+            let stem = if class == ConjugationClass::IAdjectiveIi {
+                // いい conjugates from よい: よくない, よかった
+                let prefix = lemma
+                    .strip_suffix("いい")
+                    .or_else(|| lemma.strip_suffix("よい"))?;
+                format!("{prefix}よ")
+            } else {
+                lemma.strip_suffix('い')?.to_string()
+            };
+            vec![
+                (ParadigmForm::Masu, format!("{lemma}です")),
+                (ParadigmForm::Te, format!("{stem}くて")),
+                (ParadigmForm::Ta, format!("{stem}かった")),
+                (ParadigmForm::Nai, format!("{stem}くない")),
+                (ParadigmForm::Volitional, format!("{stem}かろう")),
+                (ParadigmForm::Conditional, format!("{stem}ければ")),
+            ]
+        }
+        ConjugationClass::NaAdjective => vec![
+            (ParadigmForm::Masu, format!("{lemma}です")),
+            (ParadigmForm::Te, format!("{lemma}で")),
+            (ParadigmForm::Ta, format!("{lemma}だった")),
+            (ParadigmForm::Nai, format!("{lemma}ではない")),
+            (ParadigmForm::Volitional, format!("{lemma}だろう")),
+            (ParadigmForm::Conditional, format!("{lemma}なら")),
+        ],
+    };
+
+    let dictionary = if class == ConjugationClass::NaAdjective {
+        format!("{lemma}だ")
+    } else {
+        lemma.to_string()
+    };
+
+    let mut all = vec![ConjugatedForm {
+        form: ParadigmForm::Dictionary,
+        text: dictionary,
+    }];
+    all.extend(
+        forms
+            .into_iter()
+            .map(|(form, text)| ConjugatedForm { form, text }),
+    );
+
+    Some(Paradigm {
+        lemma: lemma.to_string(),
+        class,
+        forms: all,
+    })
+}
+
+/// Kana of a godan ending's row: (a, i, e, o) columns
+fn godan_row(ending: char) -> Option<[char; 4]> {
+    Some(match ending {
+        'う' => ['わ', 'い', 'え', 'お'],
+        'く' => ['か', 'き', 'け', 'こ'],
+        'ぐ' => ['が', 'ぎ', 'げ', 'ご'],
+        'す' => ['さ', 'し', 'せ', 'そ'],
+        'つ' => ['た', 'ち', 'て', 'と'],
+        'ぬ' => ['な', 'に', 'ね', 'の'],
+        'ぶ' => ['ば', 'び', 'べ', 'ぼ'],
+        'む' => ['ま', 'み', 'め', 'も'],
+        'る' => ['ら', 'り', 'れ', 'ろ'],
+        _ => return None,
+    })
+}
+
+fn godan_forms(lemma: &str, variant: GodanVariant) -> Option<Vec<(ParadigmForm, String)>> {
+    let ending = lemma.chars().last()?;
+    let stem = &lemma[..lemma.len() - ending.len_utf8()];
+    let [a, i, e, o] = godan_row(ending)?;
+
+    // Euphonic changes (音便) of the te/ta forms
+    let (te, ta) = match (variant, ending) {
+        (GodanVariant::Iku, _) => ("って", "った"),
+        (GodanVariant::UOnbin, _) => ("うて", "うた"),
+        (_, 'う' | 'つ' | 'る') => ("って", "った"),
+        (_, 'ぬ' | 'ぶ' | 'む') => ("んで", "んだ"),
+        (_, 'く') => ("いて", "いた"),
+        (_, 'ぐ') => ("いで", "いだ"),
+        (_, 'す') => ("して", "した"),
+        _ => return None,
+    };
+
+    let (masu_stem, imperative) = if variant == GodanVariant::Honorific {
+        ('い', format!("{stem}い"))
+    } else {
+        (i, format!("{stem}{e}"))
+    };
+
+    let mut forms = vec![
+        (ParadigmForm::Masu, format!("{stem}{masu_stem}ます")),
+        (ParadigmForm::Te, format!("{stem}{te}")),
+        (ParadigmForm::Ta, format!("{stem}{ta}")),
+    ];
+    if variant == GodanVariant::Aru {
+        // ある has a suppletive negative and no potential
+        let prefix = lemma
+            .strip_suffix("ある")
+            .or_else(|| lemma.strip_suffix("有る"))?;
+        forms.push((ParadigmForm::Nai, format!("{prefix}ない")));
+    } else {
+        forms.push((ParadigmForm::Nai, format!("{stem}{a}ない")));
+        forms.push((ParadigmForm::Potential, format!("{stem}{e}る")));
+    }
+    forms.extend([
+        (ParadigmForm::Passive, format!("{stem}{a}れる")),
+        (ParadigmForm::Causative, format!("{stem}{a}せる")),
+        (ParadigmForm::Volitional, format!("{stem}{o}う")),
+        (ParadigmForm::Conditional, format!("{stem}{e}ば")),
+        (ParadigmForm::Imperative, imperative),
+    ]);
+    Some(forms)
+}
+
+fn kuru_forms(lemma: &str) -> Option<Vec<(ParadigmForm, String)>> {
+    // 来る keeps the kanji in every form, the kana spelling changes vowel
+    let (prefix, [ki, ko, ku]) = if let Some(prefix) = lemma.strip_suffix("来る") {
+        (prefix, ["来", "来", "来"])
+    } else {
+        (lemma.strip_suffix("くる")?, ["き", "こ", "く"])
+    };
+    Some(vec![
+        (ParadigmForm::Masu, format!("{prefix}{ki}ます")),
+        (ParadigmForm::Te, format!("{prefix}{ki}て")),
+        (ParadigmForm::Ta, format!("{prefix}{ki}た")),
+        (ParadigmForm::Nai, format!("{prefix}{ko}ない")),
+        (ParadigmForm::Potential, format!("{prefix}{ko}られる")),
+        (ParadigmForm::Passive, format!("{prefix}{ko}られる")),
+        (ParadigmForm::Causative, format!("{prefix}{ko}させる")),
+        (ParadigmForm::Volitional, format!("{prefix}{ko}よう")),
+        (ParadigmForm::Conditional, format!("{prefix}{ku}れば")),
+        (ParadigmForm::Imperative, format!("{prefix}{ko}い")),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::token;
+
+    /// Forms of `lemma` in [`ParadigmForm::ALL`] order
+    fn forms(lemma: &str, class: ConjugationClass) -> Vec<(ParadigmForm, String)> {
+        conjugate(lemma, class)
+            .expect("lemma fits its class")
+            .forms
+            .into_iter()
+            .map(|f| (f.form, f.text))
+            .collect()
+    }
+
+    /// `texts` are space separated, in [`ParadigmForm::ALL`] order
+    fn expected(texts: &str) -> Vec<(ParadigmForm, String)> {
+        ParadigmForm::ALL
+            .into_iter()
+            .zip(texts.split(' ').map(str::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn godan() {
+        assert_eq!(
+            forms("書く", ConjugationClass::Godan(GodanVariant::Regular)),
+            expected(
+                "書く 書きます 書いて 書いた 書かない 書ける 書かれる 書かせる 書こう 書けば 書け"
+            )
+        );
+        assert_eq!(
+            forms("買う", ConjugationClass::Godan(GodanVariant::Regular)),
+            expected(
+                "買う 買います 買って 買った 買わない 買える 買われる 買わせる 買おう 買えば 買え"
+            )
+        );
+    }
+
+    #[test]
+    fn godan_variants() {
+        let iku = conjugate("行く", ConjugationClass::Godan(GodanVariant::Iku)).unwrap();
+        assert_eq!(iku.get(ParadigmForm::Te), Some("行って"));
+        assert_eq!(iku.get(ParadigmForm::Ta), Some("行った"));
+
+        let aru = conjugate("ある", ConjugationClass::Godan(GodanVariant::Aru)).unwrap();
+        assert_eq!(aru.get(ParadigmForm::Nai), Some("ない"));
+        assert_eq!(aru.get(ParadigmForm::Potential), None);
+
+        let nasaru = conjugate("なさる", ConjugationClass::Godan(GodanVariant::Honorific)).unwrap();
+        assert_eq!(nasaru.get(ParadigmForm::Masu), Some("なさいます"));
+        assert_eq!(nasaru.get(ParadigmForm::Imperative), Some("なさい"));
+
+        let tou = conjugate("問う", ConjugationClass::Godan(GodanVariant::UOnbin)).unwrap();
+        assert_eq!(tou.get(ParadigmForm::Te), Some("問うて"));
+    }
+
+    #[test]
+    fn ichidan() {
+        assert_eq!(
+            forms("食べる", ConjugationClass::Ichidan),
+            expected(
+                "食べる 食べます 食べて 食べた 食べない 食べられる 食べられる 食べさせる 食べよう 食べれば 食べろ"
+            )
+        );
+        let kureru = conjugate("くれる", ConjugationClass::IchidanKureru).unwrap();
+        assert_eq!(kureru.get(ParadigmForm::Imperative), Some("くれ"));
+    }
+
+    #[test]
+    fn kuru() {
+        assert_eq!(
+            forms("来る", ConjugationClass::Kuru),
+            expected("来る 来ます 来て 来た 来ない 来られる 来られる 来させる 来よう 来れば 来い")
+        );
+        let kana = conjugate("くる", ConjugationClass::Kuru).unwrap();
+        assert_eq!(kana.get(ParadigmForm::Nai), Some("こない"));
+        assert_eq!(kana.get(ParadigmForm::Masu), Some("きます"));
+    }
+
+    #[test]
+    fn suru() {
+        assert_eq!(
+            forms("する", ConjugationClass::Suru),
+            expected("する します して した しない できる される させる しよう すれば しろ")
+        );
+    }
+
+    #[test]
+    fn suru_suffix() {
+        assert_eq!(
+            forms("愛する", ConjugationClass::SuruSuffix),
+            expected(
+                "愛する 愛します 愛して 愛した 愛さない 愛せる 愛される 愛させる 愛そう 愛すれば 愛せよ"
+            )
+        );
+    }
+
+    #[test]
+    fn zuru() {
+        assert_eq!(
+            forms("信ずる", ConjugationClass::Zuru),
+            expected(
+                "信ずる 信じます 信じて 信じた 信じない 信じられる 信じられる 信じさせる 信じよう 信ずれば 信じろ"
+            )
+        );
+    }
+
+    #[test]
+    fn adjectives() {
+        let takai = conjugate("高い", ConjugationClass::IAdjective).unwrap();
+        assert_eq!(takai.get(ParadigmForm::Nai), Some("高くない"));
+        assert_eq!(takai.get(ParadigmForm::Ta), Some("高かった"));
+        assert_eq!(takai.get(ParadigmForm::Potential), None);
+
+        let ii = conjugate("いい", ConjugationClass::IAdjectiveIi).unwrap();
+        assert_eq!(ii.get(ParadigmForm::Nai), Some("よくない"));
+        assert_eq!(ii.get(ParadigmForm::Conditional), Some("よければ"));
+
+        let shizuka = conjugate("静か", ConjugationClass::NaAdjective).unwrap();
+        assert_eq!(shizuka.get(ParadigmForm::Dictionary), Some("静かだ"));
+        assert_eq!(shizuka.get(ParadigmForm::Nai), Some("静かではない"));
+    }
+
+    #[test]
+    fn lemma_must_fit_class() {
+        assert_eq!(conjugate("書く", ConjugationClass::Ichidan), None);
+        assert_eq!(conjugate("愛す", ConjugationClass::SuruSuffix), None);
+    }
+
+    #[test]
+    fn classes_from_ipadic() {
+        let class = |pos: &str, conjugation_type: &str| {
+            let t = token("x", pos, conjugation_type, "基本形", "x", "*");
+            ConjugationClass::from_ipadic(&t.conjugation_type, &t.pos)
+        };
+        assert_eq!(
+            class("動詞,自立", "五段・カ行イ音便"),
+            Some(ConjugationClass::Godan(GodanVariant::Regular))
+        );
+        assert_eq!(
+            class("動詞,自立", "五段・カ行促音便"),
+            Some(ConjugationClass::Godan(GodanVariant::Iku))
+        );
+        assert_eq!(class("動詞,自立", "一段"), Some(ConjugationClass::Ichidan));
+        assert_eq!(
+            class("動詞,自立", "カ変・来ル"),
+            Some(ConjugationClass::Kuru)
+        );
+        assert_eq!(
+            class("動詞,自立", "サ変・スル"),
+            Some(ConjugationClass::Suru)
+        );
+        assert_eq!(
+            class("動詞,自立", "サ変・－スル"),
+            Some(ConjugationClass::SuruSuffix)
+        );
+        assert_eq!(
+            class("動詞,自立", "サ変・－ズル"),
+            Some(ConjugationClass::Zuru)
+        );
+        assert_eq!(
+            class("形容詞,自立", "形容詞・イイ"),
+            Some(ConjugationClass::IAdjectiveIi)
+        );
+        assert_eq!(
+            class("形容詞,自立", "形容詞・アウオ段"),
+            Some(ConjugationClass::IAdjective)
+        );
+        assert_eq!(
+            class("名詞,形容動詞語幹", "*"),
+            Some(ConjugationClass::NaAdjective)
+        );
+        assert_eq!(class("名詞,一般", "*"), None);
+    }
+
+    #[test]
+    fn paradigm_of_single_kanji_suru_verb() {
+        let ai = token(
+            "愛さ",
+            "動詞,自立",
+            "サ変・－スル",
+            "未然形",
+            "愛する",
+            "アイサ",
+        );
+        let paradigm = token_paradigm(&ai).unwrap();
+        assert_eq!(paradigm.lemma, "愛する");
+        assert_eq!(paradigm.get(ParadigmForm::Nai), Some("愛さない"));
+        assert_eq!(paradigm.get(ParadigmForm::Potential), Some("愛せる"));
+        assert_eq!(paradigm.get(ParadigmForm::Imperative), Some("愛せよ"));
+    }
+}
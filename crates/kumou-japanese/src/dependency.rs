@@ -0,0 +1,322 @@
+use serde::{Deserialize, Serialize};
+
+use crate::bunsetsu::{Bunsetsu, chunk_tokens};
+use crate::pos::{PosMajor, PosSub};
+use crate::tokenizer::AnalyzedToken;
+
+/// How a bunsetsu relates to the one it modifies
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum DependencyRelation {
+    /// は, も: attaches to the main predicate
+    Topic,
+    /// が
+    Subject,
+    /// を
+    Object,
+    /// Other case particles: に, で, へ, と, から, まで, より
+    Oblique,
+    /// の between nouns
+    Genitive,
+    /// Modifies a noun: relative clauses, 連体詞, な-adjectives
+    Adnominal,
+    /// Conjunctive particles and て-forms linking clauses
+    Conjunctive,
+    /// Adverbs and other modifiers of a predicate
+    Adverbial,
+    /// The main predicate, which modifies nothing
+    Root,
+}
+
+impl DependencyRelation {
+    /// English label
+    pub fn label(&self) -> &'static str {
+        match self {
+            DependencyRelation::Topic => "Topic",
+            DependencyRelation::Subject => "Subject",
+            DependencyRelation::Object => "Object",
+            DependencyRelation::Oblique => "Oblique",
+            DependencyRelation::Genitive => "Genitive",
+            DependencyRelation::Adnominal => "Noun modifier",
+            DependencyRelation::Conjunctive => "Clause link",
+            DependencyRelation::Adverbial => "Adverbial",
+            DependencyRelation::Root => "Root",
+        }
+    }
+}
+
+/// A bunsetsu and the bunsetsu it modifies
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DependencyNode {
+    pub bunsetsu: Bunsetsu,
+    /// Index of the modified node, `None` for the root
+    pub head: Option<usize>,
+    pub relation: DependencyRelation,
+}
+
+/// Bunsetsu dependency tree of a sentence (係り受け)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DependencyTree {
+    /// Nodes in sentence order
+    pub nodes: Vec<DependencyNode>,
+}
+
+impl DependencyTree {
+    /// Index of the root node
+    pub fn root(&self) -> Option<usize> {
+        self.nodes.iter().position(|n| n.head.is_none())
+    }
+
+    /// Indices of the nodes that modify `index`
+    pub fn dependents(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(move |(_, n)| n.head == Some(index))
+            .map(|(i, _)| i)
+    }
+}
+
+/// Attach each bunsetsu to the bunsetsu it modifies
+///
+/// Japanese is head-final, so every bunsetsu modifies one to its right and
+/// the last is the root. Working from the end, the candidates for a bunsetsu
+/// are the next one and that one's chain of heads, which keeps arcs from
+/// crossing. Noun modifiers take the nearest noun, topics the main
+/// predicate, and everything else the nearest predicate.
+pub fn parse_dependencies(tokens: &[AnalyzedToken]) -> DependencyTree {
+    let chunks = chunk_tokens(tokens);
+    let count = chunks.len();
+    let mut heads: Vec<Option<usize>> = vec![None; count];
+    let mut relations = vec![DependencyRelation::Root; count];
+
+    for i in (0..count.saturating_sub(1)).rev() {
+        let relation = relation_of(tokens, &chunks, i);
+        let candidates: Vec<usize> =
+            std::iter::successors(Some(i + 1), |&j| heads[j]).collect();
+
+        let head = match relation {
+            DependencyRelation::Topic => candidates.last().copied(),
+            DependencyRelation::Genitive | DependencyRelation::Adnominal => candidates
+                .iter()
+                .copied()
+                .find(|&j| is_nominal(&tokens[chunks[j].head])),
+            _ => candidates
+                .iter()
+                .copied()
+                .find(|&j| is_predicate(&tokens[chunks[j].start..chunks[j].end])),
+        };
+
+        heads[i] = Some(head.unwrap_or(i + 1));
+        relations[i] = relation;
+    }
+
+    DependencyTree {
+        nodes: chunks
+            .into_iter()
+            .zip(heads)
+            .zip(relations)
+            .map(|((bunsetsu, head), relation)| DependencyNode {
+                bunsetsu,
+                head,
+                relation,
+            })
+            .collect(),
+    }
+}
+
+fn relation_of(tokens: &[AnalyzedToken], chunks: &[Bunsetsu], i: usize) -> DependencyRelation {
+    let chunk = &chunks[i];
+    let head = &tokens[chunk.head];
+    let chunk_tokens = &tokens[chunk.start..chunk.end];
+
+    if let Some(function) = chunk.function.map(|f| &tokens[f]) {
+        let surface = function.surface.as_str();
+        match (&function.pos.major, &function.pos.sub1) {
+            (PosMajor::Particle, PosSub::BindingParticle) if matches!(surface, "は" | "も") => {
+                return DependencyRelation::Topic;
+            }
+            (PosMajor::Particle, PosSub::Adnominalizer) => return DependencyRelation::Genitive,
+            (PosMajor::Particle, PosSub::CaseParticle) => {
+                return match surface {
+                    "が" => DependencyRelation::Subject,
+                    "を" => DependencyRelation::Object,
+                    "の" => DependencyRelation::Genitive,
+                    _ => DependencyRelation::Oblique,
+                };
+            }
+            (PosMajor::Particle, PosSub::ConjunctiveParticle) => return DependencyRelation::Conjunctive,
+            _ => {}
+        }
+    }
+
+    if head.pos.major == PosMajor::Adnominal && chunk.function.is_none() {
+        return DependencyRelation::Adnominal;
+    }
+
+    // A clause ending in an attributive form before a noun: 買った 本
+    let before_noun = chunks
+        .get(i + 1)
+        .is_some_and(|next| is_nominal(&tokens[next.head]));
+    let last = chunk_tokens.iter().rev().find(|t| !t.pos.major.is_symbol());
+    let attributive = last.is_some_and(|t| {
+        matches!(
+            t.pos.major,
+            PosMajor::Verb | PosMajor::Adjective | PosMajor::AuxiliaryVerb
+        ) && t.conjugation_form.is_attributive()
+    });
+    let ends_clause = chunk_tokens.last().is_some_and(|t| t.pos.major.is_symbol());
+    if before_noun && attributive && !ends_clause {
+        return DependencyRelation::Adnominal;
+    }
+
+    DependencyRelation::Adverbial
+}
+
+fn is_nominal(token: &AnalyzedToken) -> bool {
+    token.pos.major.is_nominal()
+}
+
+/// Verbs, adjectives, and nouns followed by a copula such as だ or です
+fn is_predicate(tokens: &[AnalyzedToken]) -> bool {
+    tokens.iter().any(|t| {
+        matches!(
+            t.pos.major,
+            PosMajor::Verb | PosMajor::Adjective | PosMajor::AuxiliaryVerb
+        ) && !matches!(t.pos.sub1, PosSub::Dependent | PosSub::Suffix)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::token;
+
+    fn t(surface: &str, pos: &str) -> AnalyzedToken {
+        token(surface, pos, "*", "*", surface, "*")
+    }
+
+    fn conjugated(surface: &str, pos: &str, form: &str, base: &str) -> AnalyzedToken {
+        token(surface, pos, "*", form, base, "*")
+    }
+
+    fn heads(tree: &DependencyTree) -> Vec<Option<usize>> {
+        tree.nodes.iter().map(|n| n.head).collect()
+    }
+
+    fn relations(tree: &DependencyTree) -> Vec<DependencyRelation> {
+        tree.nodes.iter().map(|n| n.relation).collect()
+    }
+
+    /// No two arcs cross: an arc from inside another's span ends inside it
+    fn assert_projective(tree: &DependencyTree) {
+        let arcs: Vec<(usize, usize)> = heads(tree)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, head)| head.map(|h| (i, h)))
+            .collect();
+        for &(a, b) in &arcs {
+            for &(c, d) in &arcs {
+                assert!(!(a < c && c < b && b < d), "{a}→{b} crosses {c}→{d}");
+            }
+        }
+    }
+
+    #[test]
+    fn topic_and_object() {
+        // 私は 本を 読む
+        let tree = parse_dependencies(&[
+            t("私", "名詞,代名詞"),
+            t("は", "助詞,係助詞"),
+            t("本", "名詞,一般"),
+            t("を", "助詞,格助詞"),
+            conjugated("読む", "動詞,自立", "基本形", "読む"),
+        ]);
+        assert_eq!(heads(&tree), [Some(2), Some(2), None]);
+        assert_eq!(
+            relations(&tree),
+            [
+                DependencyRelation::Topic,
+                DependencyRelation::Object,
+                DependencyRelation::Root
+            ]
+        );
+        assert_eq!(tree.root(), Some(2));
+        assert_eq!(tree.dependents(2).collect::<Vec<_>>(), [0, 1]);
+    }
+
+    #[test]
+    fn relative_clause() {
+        // 私は 昨日 買った 本を 読んだ
+        let tree = parse_dependencies(&[
+            t("私", "名詞,代名詞"),
+            t("は", "助詞,係助詞"),
+            t("昨日", "名詞,副詞可能"),
+            conjugated("買っ", "動詞,自立", "連用タ接続", "買う"),
+            conjugated("た", "助動詞", "基本形", "た"),
+            t("本", "名詞,一般"),
+            t("を", "助詞,格助詞"),
+            conjugated("読ん", "動詞,自立", "連用タ接続", "読む"),
+            conjugated("だ", "助動詞", "基本形", "だ"),
+        ]);
+        // 昨日 modifies 買った, 買った modifies 本, the topic the main verb
+        assert_eq!(heads(&tree), [Some(4), Some(2), Some(3), Some(4), None]);
+        assert_eq!(
+            relations(&tree),
+            [
+                DependencyRelation::Topic,
+                DependencyRelation::Adverbial,
+                DependencyRelation::Adnominal,
+                DependencyRelation::Object,
+                DependencyRelation::Root
+            ]
+        );
+        assert_projective(&tree);
+    }
+
+    #[test]
+    fn genitive_takes_the_nearest_noun() {
+        // 友達の 兄の 車が 来た
+        let tree = parse_dependencies(&[
+            t("友達", "名詞,一般"),
+            t("の", "助詞,連体化"),
+            t("兄", "名詞,一般"),
+            t("の", "助詞,連体化"),
+            t("車", "名詞,一般"),
+            t("が", "助詞,格助詞"),
+            conjugated("来", "動詞,自立", "連用形", "来る"),
+            conjugated("た", "助動詞", "基本形", "た"),
+        ]);
+        assert_eq!(heads(&tree), [Some(1), Some(2), Some(3), None]);
+        assert_eq!(relations(&tree)[2], DependencyRelation::Subject);
+        assert_projective(&tree);
+    }
+
+    #[test]
+    fn punctuation_ends_a_clause() {
+        // 寒い、 部屋だ: the comma keeps 寒い from modifying 部屋
+        for comma in ["記号,読点", "補助記号,読点"] {
+            let tree = parse_dependencies(&[
+                conjugated("寒い", "形容詞,自立", "基本形", "寒い"),
+                t("、", comma),
+                t("部屋", "名詞,一般"),
+                conjugated("だ", "助動詞", "基本形", "だ"),
+            ]);
+            assert_eq!(heads(&tree), [Some(1), None], "{comma}");
+            assert_eq!(relations(&tree)[0], DependencyRelation::Adverbial, "{comma}");
+        }
+
+        // Without it, 寒い 部屋 is a noun phrase
+        let tree = parse_dependencies(&[
+            conjugated("寒い", "形容詞,自立", "基本形", "寒い"),
+            t("部屋", "名詞,一般"),
+            conjugated("だ", "助動詞", "基本形", "だ"),
+        ]);
+        assert_eq!(relations(&tree)[0], DependencyRelation::Adnominal);
+    }
+
+    #[test]
+    fn empty_sentence() {
+        assert!(parse_dependencies(&[]).nodes.is_empty());
+    }
+}
@@ -1,11 +1,20 @@
 use serde::{Deserialize, Serialize};
 
+use crate::difficulty::Difficulty;
+use crate::register::Register;
+
 /// A single utterance in a dialogue
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Utterance {
     pub turn_num: u32,
     pub speaker: String,
     pub utterance: String,
+    /// Estimated difficulty, filled in by the server when scored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<Difficulty>,
+    /// Politeness register, filled in by the server when classified
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub register: Option<Register>,
 }
 
 /// A complete dialogue between speakers
@@ -16,6 +25,9 @@ pub struct Dialogue {
     pub dialogue_id: u32,
     pub dialogue_length: u32,
     pub utterances: Vec<Utterance>,
+    /// Estimated difficulty, filled in by the server when scored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<Difficulty>,
 }
 
 /// Summary info for a topic (without full dialogue data)
@@ -0,0 +1,374 @@
+use serde::{Deserialize, Serialize};
+
+use crate::dependency::DependencyTree;
+use crate::grammar::detect_grammar;
+use crate::inflection::inflection_chains;
+use crate::jlpt::{JlptLevel, JlptLists};
+use crate::kana::is_kanji;
+use crate::pos::{PosMajor, PosSub};
+use crate::tokenizer::AnalyzedSentence;
+
+/// Tokens at which sentence length stops adding difficulty
+const LONG_SENTENCE_TOKENS: f32 = 30.0;
+/// Grammar features at which grammar stops adding difficulty
+const COMPLEX_GRAMMAR_FEATURES: f32 = 8.0;
+
+const LENGTH_WEIGHT: f32 = 0.2;
+const KANJI_WEIGHT: f32 = 0.25;
+const RARITY_WEIGHT: f32 = 0.35;
+const GRAMMAR_WEIGHT: f32 = 0.2;
+
+/// Estimated difficulty of a sentence or dialogue
+///
+/// Each component is between 0 and 1; `score` weighs them into 0–100. When
+/// rarity is unknown, the other components are weighed on their own.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct Difficulty {
+    pub score: f32,
+    /// Token count relative to a long sentence
+    pub length: f32,
+    /// Share of kanji among the non-symbol characters
+    pub kanji_density: f32,
+    /// Average JLPT level of the content words, unlisted words counting as
+    /// hardest; `None` without JLPT lists or content words
+    pub rarity: Option<f32>,
+    /// Inflections, grammar patterns and clause nesting
+    pub grammar: f32,
+}
+
+impl Difficulty {
+    fn from_components(length: f32, kanji_density: f32, rarity: Option<f32>, grammar: f32) -> Self {
+        let weighted =
+            LENGTH_WEIGHT * length + KANJI_WEIGHT * kanji_density + GRAMMAR_WEIGHT * grammar;
+        let total = LENGTH_WEIGHT + KANJI_WEIGHT + GRAMMAR_WEIGHT;
+        let score = match rarity {
+            Some(rarity) => 100.0 * (weighted + RARITY_WEIGHT * rarity) / (total + RARITY_WEIGHT),
+            None => 100.0 * weighted / total,
+        };
+        Self {
+            score,
+            length,
+            kanji_density,
+            rarity,
+            grammar,
+        }
+    }
+
+    /// Estimate the difficulty of an analyzed sentence
+    ///
+    /// Vocabulary rarity is looked up in `jlpt`. Without lists every word
+    /// would count as unlisted, so rarity is left out of the score instead.
+    pub fn of_sentence(sentence: &AnalyzedSentence, jlpt: Option<&JlptLists>) -> Self {
+        let length = (sentence.tokens.len() as f32 / LONG_SENTENCE_TOKENS).min(1.0);
+
+        let letters: Vec<char> = sentence
+            .tokens
+            .iter()
+            .filter(|t| !t.pos.major.is_symbol())
+            .flat_map(|t| t.surface.chars())
+            .collect();
+        let kanji_density = ratio(
+            letters.iter().filter(|&&c| is_kanji(c)).count(),
+            letters.len(),
+        );
+
+        let levels: Vec<f32> = sentence
+            .tokens
+            .iter()
+            .filter(|_| jlpt.is_some())
+            .filter(|t| {
+                matches!(
+                    t.pos.major,
+                    PosMajor::Noun
+                        | PosMajor::Verb
+                        | PosMajor::Adjective
+                        | PosMajor::Adverb
+                        | PosMajor::AdjectivalNoun
+                ) && !matches!(t.pos.sub1, PosSub::Dependent | PosSub::Suffix | PosSub::Number)
+            })
+            .map(|t| {
+                jlpt.and_then(|lists| lists.token_level(t))
+                    .map_or(1.0, level_weight)
+            })
+            .collect();
+        let rarity = (!levels.is_empty()).then(|| levels.iter().sum::<f32>() / levels.len() as f32);
+
+        let steps: usize = inflection_chains(sentence)
+            .iter()
+            .map(|c| c.steps.len())
+            .sum();
+        let patterns = detect_grammar(sentence).len();
+        let nesting = tree_depth(&sentence.dependencies).saturating_sub(2);
+        let grammar = ((steps + patterns + nesting) as f32 / COMPLEX_GRAMMAR_FEATURES).min(1.0);
+
+        Self::from_components(length, kanji_density, rarity, grammar)
+    }
+
+    /// Combine utterance difficulties into a dialogue difficulty
+    ///
+    /// Components are averaged, so a dialogue is as hard as its typical
+    /// utterance rather than its hardest one.
+    pub fn of_dialogue(utterances: &[Difficulty]) -> Self {
+        if utterances.is_empty() {
+            return Self::default();
+        }
+        let mean = |f: fn(&Difficulty) -> f32| {
+            utterances.iter().map(f).sum::<f32>() / utterances.len() as f32
+        };
+        // Utterances without a rarity do not pull the average down
+        let rarities: Vec<f32> = utterances.iter().filter_map(|d| d.rarity).collect();
+        let rarity = (!rarities.is_empty())
+            .then(|| rarities.iter().sum::<f32>() / rarities.len() as f32);
+        Self::from_components(
+            mean(|d| d.length),
+            mean(|d| d.kanji_density),
+            rarity,
+            mean(|d| d.grammar),
+        )
+    }
+
+    pub fn level(&self) -> DifficultyLevel {
+        DifficultyLevel::from_score(self.score)
+    }
+}
+
+/// Coarse difficulty band used for filtering
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum DifficultyLevel {
+    /// Score below 30
+    Beginner,
+    /// Score from 30 to below 50
+    Intermediate,
+    /// Score of 50 and above
+    Advanced,
+}
+
+impl DifficultyLevel {
+    pub const ALL: [DifficultyLevel; 3] = [
+        DifficultyLevel::Beginner,
+        DifficultyLevel::Intermediate,
+        DifficultyLevel::Advanced,
+    ];
+
+    pub fn from_score(score: f32) -> Self {
+        if score < 30.0 {
+            DifficultyLevel::Beginner
+        } else if score < 50.0 {
+            DifficultyLevel::Intermediate
+        } else {
+            DifficultyLevel::Advanced
+        }
+    }
+
+    /// Identifier used in form values, e.g. `beginner`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DifficultyLevel::Beginner => "beginner",
+            DifficultyLevel::Intermediate => "intermediate",
+            DifficultyLevel::Advanced => "advanced",
+        }
+    }
+
+    /// Label shown to users
+    pub fn label(&self) -> &'static str {
+        match self {
+            DifficultyLevel::Beginner => "Beginner",
+            DifficultyLevel::Intermediate => "Intermediate",
+            DifficultyLevel::Advanced => "Advanced",
+        }
+    }
+}
+
+/// Order of a dialogue listing
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum DifficultySort {
+    /// Corpus order
+    #[default]
+    None,
+    EasiestFirst,
+    HardestFirst,
+}
+
+impl DifficultySort {
+    pub const ALL: [DifficultySort; 3] = [
+        DifficultySort::None,
+        DifficultySort::EasiestFirst,
+        DifficultySort::HardestFirst,
+    ];
+
+    /// Identifier used in form values, e.g. `easiest-first`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DifficultySort::None => "none",
+            DifficultySort::EasiestFirst => "easiest-first",
+            DifficultySort::HardestFirst => "hardest-first",
+        }
+    }
+
+    /// Label shown to users
+    pub fn label(&self) -> &'static str {
+        match self {
+            DifficultySort::None => "Corpus order",
+            DifficultySort::EasiestFirst => "Easiest first",
+            DifficultySort::HardestFirst => "Hardest first",
+        }
+    }
+}
+
+fn ratio(part: usize, whole: usize) -> f32 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f32 / whole as f32
+    }
+}
+
+fn level_weight(level: JlptLevel) -> f32 {
+    match level {
+        JlptLevel::N5 => 0.0,
+        JlptLevel::N4 => 0.25,
+        JlptLevel::N3 => 0.5,
+        JlptLevel::N2 => 0.75,
+        JlptLevel::N1 => 1.0,
+    }
+}
+
+/// Longest chain of bunsetsu from a leaf to the root, counting both ends
+fn tree_depth(tree: &DependencyTree) -> usize {
+    (0..tree.nodes.len())
+        .map(|mut index| {
+            let mut depth = 1;
+            while let Some(head) = tree.nodes[index].head {
+                depth += 1;
+                index = head;
+            }
+            depth
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bunsetsu::Bunsetsu;
+    use crate::dependency::{DependencyNode, DependencyRelation};
+    use crate::test_support::{sentence, token};
+
+    /// 私は学生です
+    fn watashi_wa_gakusei_desu() -> AnalyzedSentence {
+        sentence(vec![
+            token("私", "名詞,代名詞", "*", "*", "私", "ワタシ"),
+            token("は", "助詞,係助詞", "*", "*", "は", "ハ"),
+            token("学生", "名詞,一般", "*", "*", "学生", "ガクセイ"),
+            token("です", "助動詞", "特殊・デス", "基本形", "です", "デス"),
+        ])
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn components_of_sentence() {
+        let difficulty = Difficulty::of_sentence(&watashi_wa_gakusei_desu(), None);
+        assert_close(difficulty.length, 4.0 / 30.0);
+        assert_close(difficulty.kanji_density, 0.5);
+        assert_eq!(difficulty.rarity, None);
+        assert_close(difficulty.grammar, 0.0);
+    }
+
+    #[test]
+    fn rarity_is_left_out_without_lists() {
+        let difficulty = Difficulty::of_sentence(&watashi_wa_gakusei_desu(), None);
+        let expected = 100.0 * (0.2 * 4.0 / 30.0 + 0.25 * 0.5) / 0.65;
+        assert_close(difficulty.score, expected);
+        assert_eq!(difficulty.level(), DifficultyLevel::Beginner);
+    }
+
+    #[test]
+    fn rarity_from_lists() {
+        let lists = JlptLists::parse("私,N5", "").unwrap();
+        let difficulty = Difficulty::of_sentence(&watashi_wa_gakusei_desu(), Some(&lists));
+        // 私 is N5, 学生 is unlisted and counts as hardest
+        assert_eq!(difficulty.rarity, Some(0.5));
+        assert_close(
+            difficulty.score,
+            100.0 * (0.2 * 4.0 / 30.0 + 0.25 * 0.5 + 0.35 * 0.5),
+        );
+
+        let lists = JlptLists::parse("私,N5\n学生,N4", "").unwrap();
+        let difficulty = Difficulty::of_sentence(&watashi_wa_gakusei_desu(), Some(&lists));
+        assert_eq!(difficulty.rarity, Some(0.125));
+    }
+
+    #[test]
+    fn no_rarity_without_content_words() {
+        let lists = JlptLists::parse("私,N5", "").unwrap();
+        let sentence = sentence(vec![
+            token("ね", "助詞,終助詞", "*", "*", "ね", "ネ"),
+            token("。", "記号,句点", "*", "*", "。", "。"),
+        ]);
+        let difficulty = Difficulty::of_sentence(&sentence, Some(&lists));
+        assert_eq!(difficulty.rarity, None);
+        assert_close(difficulty.kanji_density, 0.0);
+    }
+
+    #[test]
+    fn dialogue_averages_utterances() {
+        let easy = Difficulty::from_components(0.0, 0.0, Some(0.0), 0.0);
+        let hard = Difficulty::from_components(1.0, 1.0, None, 1.0);
+        let dialogue = Difficulty::of_dialogue(&[easy, hard]);
+        assert_close(dialogue.length, 0.5);
+        assert_close(dialogue.kanji_density, 0.5);
+        // Only the utterance with a rarity counts towards it
+        assert_eq!(dialogue.rarity, Some(0.0));
+        assert_close(dialogue.grammar, 0.5);
+        assert_close(dialogue.score, 100.0 * (0.2 + 0.25 + 0.2) * 0.5);
+
+        assert_eq!(Difficulty::of_dialogue(&[]), Difficulty::default());
+    }
+
+    #[test]
+    fn level_boundaries() {
+        let cases = [
+            (0.0, DifficultyLevel::Beginner),
+            (29.9, DifficultyLevel::Beginner),
+            (30.0, DifficultyLevel::Intermediate),
+            (49.9, DifficultyLevel::Intermediate),
+            (50.0, DifficultyLevel::Advanced),
+            (100.0, DifficultyLevel::Advanced),
+        ];
+        for (score, level) in cases {
+            assert_eq!(DifficultyLevel::from_score(score), level, "{score}");
+        }
+    }
+
+    fn tree(heads: &[Option<usize>]) -> DependencyTree {
+        let node = |head| DependencyNode {
+            bunsetsu: Bunsetsu {
+                start: 0,
+                end: 0,
+                head: 0,
+                function: None,
+                surface: String::new(),
+            },
+            head,
+            relation: DependencyRelation::Adverbial,
+        };
+        DependencyTree {
+            nodes: heads.iter().copied().map(node).collect(),
+        }
+    }
+
+    #[test]
+    fn depth_of_tree() {
+        assert_eq!(tree_depth(&tree(&[])), 0);
+        assert_eq!(tree_depth(&tree(&[None])), 1);
+        assert_eq!(tree_depth(&tree(&[Some(2), Some(2), None])), 2);
+        assert_eq!(tree_depth(&tree(&[Some(1), Some(2), None])), 3);
+        assert_eq!(tree_depth(&tree(&[Some(3), Some(2), Some(3), None])), 3);
+    }
+}
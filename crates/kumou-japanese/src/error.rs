@@ -5,6 +5,90 @@ pub enum AnalysisError {
     #[error("Failed to initialize tokenizer: {0}")]
     TokenizerInit(String),
 
+    #[error("Failed to load the shared analyzer: {0}")]
+    SharedAnalyzer(String),
+
+    #[error("Unknown dictionary: {0}")]
+    UnknownDictionary(String),
+
+    #[error("Dictionary '{0}' is not available in this build (enable the '{1}' feature)")]
+    DictionaryUnavailable(String, String),
+
+    #[error("Unknown segmentation mode: {0}")]
+    UnknownMode(String),
+
+    #[error("Unknown normalization: {0}")]
+    UnknownNormalization(String),
+
+    #[error("Unknown label language: {0}")]
+    UnknownLabelLanguage(String),
+
+    #[error("Failed to read user dictionary: {0}")]
+    UserDictionaryRead(String),
+
+    #[error("User dictionary line {line}: expected 4 columns (surface, POS, reading, base form), found {found}")]
+    UserDictionaryColumns { line: usize, found: usize },
+
+    #[error("User dictionary line {line}: surface is empty")]
+    UserDictionaryEmptySurface { line: usize },
+
+    #[error("User dictionary line {line}: '{surface}' is already defined")]
+    UserDictionaryDuplicate { line: usize, surface: String },
+
+    #[error("User dictionary line {line}: unknown part-of-speech '{pos}'")]
+    UserDictionaryPos { line: usize, pos: String },
+
+    #[error("User dictionary line {line}: reading '{reading}' must be non-empty katakana")]
+    UserDictionaryReading { line: usize, reading: String },
+
+    #[error("Failed to read accent dictionary: {0}")]
+    AccentDictionaryRead(String),
+
+    #[error("Accent dictionary line {line}: expected 3 columns (lemma, reading, nucleus), found {found}")]
+    AccentDictionaryColumns { line: usize, found: usize },
+
+    #[error("Accent dictionary line {line}: reading '{reading}' must be non-empty katakana")]
+    AccentDictionaryReading { line: usize, reading: String },
+
+    #[error("Accent dictionary line {line}: nucleus '{nucleus}' must be a mora position within the reading, or 0")]
+    AccentDictionaryNucleus { line: usize, nucleus: String },
+
+    #[error("Accent dictionary line {line}: '{lemma}' ({reading}) is already defined")]
+    AccentDictionaryDuplicate { line: usize, lemma: String, reading: String },
+
+    #[error("Failed to read JLPT list: {0}")]
+    JlptListRead(String),
+
+    #[error("JLPT list line {line}: expected 2 columns (item, level), found {found}")]
+    JlptColumns { line: usize, found: usize },
+
+    #[error("JLPT list line {line}: unknown level '{level}', expected N5 to N1")]
+    JlptLevel { line: usize, level: String },
+
+    #[error("JLPT list line {line}: '{entry}' is not a word or a single kanji")]
+    JlptEntry { line: usize, entry: String },
+
+    #[error("Failed to read KANJIDIC2 file: {0}")]
+    KanjiDictionaryRead(String),
+
+    #[error("Invalid KANJIDIC2 XML: {0}")]
+    KanjiDictionaryParse(String),
+
+    #[error("Failed to read JMdict file: {0}")]
+    JmdictRead(String),
+
+    #[error("Invalid JMdict XML: {0}")]
+    JmdictParse(String),
+
+    #[error("Invalid label catalog: {0}")]
+    LabelCatalogParse(String),
+
+    #[error("Invalid grammar rules: {0}")]
+    GrammarRuleParse(String),
+
+    #[error("Grammar rule '{id}': {reason}")]
+    GrammarRuleInvalid { id: String, reason: String },
+
     #[error("Tokenization failed: {0}")]
     Tokenization(String),
 
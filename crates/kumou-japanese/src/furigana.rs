@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+
+use crate::kana::{is_kana, is_kanji, katakana_to_hiragana};
+use crate::normalize::{Normalization, NormalizedText};
+use crate::tokenizer::{AnalyzedSentence, AnalyzedToken};
+
+/// A run of a token's surface with the reading shown above it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FuriganaSegment {
+    /// Part of the surface, either a kanji run or a kana run
+    pub text: String,
+    /// Hiragana reading for kanji runs, `None` for kana that need no furigana
+    pub reading: Option<String>,
+}
+
+impl FuriganaSegment {
+    fn plain(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            reading: None,
+        }
+    }
+}
+
+/// Split a token into kanji/kana segments with per-segment readings
+pub fn token_furigana(token: &AnalyzedToken) -> Vec<FuriganaSegment> {
+    align_furigana(&token.surface, &token.reading)
+}
+
+/// Split a token of `sentence` into segments of the sentence's original text
+///
+/// The reading is aligned to the token's surface, which normalization may
+/// have changed, and each segment then shows the original characters it came
+/// from: ＡＢＣ社 stays full-width rather than becoming ABC社. A token that
+/// cannot be traced back, like 平 of ㍻ → 平成, shows the original text plain.
+pub fn original_furigana(
+    sentence: &AnalyzedSentence,
+    token: &AnalyzedToken,
+) -> Vec<FuriganaSegment> {
+    let original = sentence
+        .text
+        .get(token.byte_start..token.byte_end)
+        .unwrap_or(&token.surface);
+    if original == token.surface {
+        return token_furigana(token);
+    }
+    let Some(normalized) = [Normalization::Nfc, Normalization::Nfkc]
+        .into_iter()
+        .map(|normalization| NormalizedText::new(original, normalization))
+        .find(|normalized| normalized.text == token.surface)
+    else {
+        return vec![FuriganaSegment::plain(original)];
+    };
+
+    let chars: Vec<char> = original.chars().collect();
+    let mut segments: Vec<FuriganaSegment> = Vec::new();
+    // Normalized chars consumed, and original chars shown so far
+    let (mut position, mut shown) = (0, 0);
+    for segment in token_furigana(token) {
+        let length = segment.text.chars().count();
+        let span = normalized.original_span(position..position + length);
+        position += length;
+
+        let start = span.start.max(shown);
+        match segments.last_mut() {
+            // The segment shares its original character with the one before
+            Some(previous) if start >= span.end => {
+                if let (Some(reading), Some(more)) = (&mut previous.reading, segment.reading) {
+                    reading.push_str(&more);
+                }
+            }
+            _ => segments.push(FuriganaSegment {
+                text: chars[start..span.end].iter().collect(),
+                reading: segment.reading,
+            }),
+        }
+        shown = shown.max(span.end);
+    }
+    segments
+}
+
+/// Align a katakana `reading` to the kanji runs of `surface`
+///
+/// Kana in the surface anchor the alignment, e.g. 食べる + タベル gives
+/// 食[た] べる. When the reading cannot be aligned the whole surface gets
+/// the whole reading. Surfaces without kanji, or with no known reading
+/// (`*`), come back as a single segment without furigana.
+pub fn align_furigana(surface: &str, reading: &str) -> Vec<FuriganaSegment> {
+    if reading.is_empty() || reading == "*" || !surface.chars().any(is_kanji) {
+        return vec![FuriganaSegment::plain(surface)];
+    }
+
+    let reading: Vec<char> = katakana_to_hiragana(reading).chars().collect();
+    let runs = split_runs(surface);
+
+    let mut readings = vec![String::new(); runs.len()];
+    if align_runs(&runs, 0, &reading, 0, &mut readings) {
+        runs.iter()
+            .zip(readings)
+            .map(|(run, reading)| FuriganaSegment {
+                text: run.text.clone(),
+                reading: (!run.is_kana).then_some(reading),
+            })
+            .collect()
+    } else {
+        vec![FuriganaSegment {
+            text: surface.to_string(),
+            reading: Some(reading.into_iter().collect()),
+        }]
+    }
+}
+
+struct Run {
+    text: String,
+    is_kana: bool,
+}
+
+/// Split `surface` into alternating kana and non-kana runs
+fn split_runs(surface: &str) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    for c in surface.chars() {
+        let is_kana = is_kana(c);
+        match runs.last_mut() {
+            Some(run) if run.is_kana == is_kana => run.text.push(c),
+            _ => runs.push(Run {
+                text: c.to_string(),
+                is_kana,
+            }),
+        }
+    }
+    runs
+}
+
+/// Backtracking match of runs against the reading, shortest kanji readings first
+fn align_runs(
+    runs: &[Run],
+    index: usize,
+    reading: &[char],
+    pos: usize,
+    readings: &mut [String],
+) -> bool {
+    let Some(run) = runs.get(index) else {
+        return pos == reading.len();
+    };
+
+    if run.is_kana {
+        let kana: Vec<char> = katakana_to_hiragana(&run.text).chars().collect();
+        return reading[pos..].starts_with(&kana)
+            && align_runs(runs, index + 1, reading, pos + kana.len(), readings);
+    }
+
+    for end in pos + 1..=reading.len() {
+        if align_runs(runs, index + 1, reading, end, readings) {
+            readings[index] = reading[pos..end].iter().collect();
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::token;
+
+    fn segment(text: &str, reading: Option<&str>) -> FuriganaSegment {
+        FuriganaSegment {
+            text: text.to_string(),
+            reading: reading.map(str::to_string),
+        }
+    }
+
+    /// Segments as `text(reading)` or `text`, separated by spaces
+    fn aligned(surface: &str, reading: &str) -> String {
+        align_furigana(surface, reading)
+            .into_iter()
+            .map(|s| match s.reading {
+                Some(reading) => format!("{}({reading})", s.text),
+                None => s.text,
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn alignment() {
+        let cases = [
+            // Okurigana anchor the kanji reading
+            ("食べる", "タベル", "食(た) べる"),
+            ("大人しい", "オトナシイ", "大人(おとな) しい"),
+            ("お茶", "オチャ", "お 茶(ちゃ)"),
+            ("取り引き", "トリヒキ", "取(と) り 引(ひ) き"),
+            ("引き出し", "ヒキダシ", "引(ひ) き 出(だ) し"),
+            // Katakana in the surface match the reading too
+            ("ドイツ語", "ドイツゴ", "ドイツ 語(ご)"),
+            // A kanji run takes the whole reading of the word
+            ("今日", "キョウ", "今日(きょう)"),
+            ("先生", "センセイ", "先生(せんせい)"),
+            // The い in the reading is tried as 可愛's before the okurigana
+            ("可愛い", "カワイイ", "可愛(かわい) い"),
+            // No kanji or no reading: nothing to show
+            ("ありがとう", "アリガトウ", "ありがとう"),
+            ("東京", "*", "東京"),
+            ("東京", "", "東京"),
+        ];
+        for (surface, reading, expected) in cases {
+            assert_eq!(aligned(surface, reading), expected, "{surface} {reading}");
+        }
+    }
+
+    #[test]
+    fn unalignable_reading_covers_the_whole_surface() {
+        // The okurigana る is not in the reading
+        assert_eq!(aligned("食べる", "ノム"), "食べる(のむ)");
+        // The reading runs out before the kana
+        assert_eq!(aligned("取り引き", "トリ"), "取り引き(とり)");
+    }
+
+    /// Furigana of a token read as `surface` from the whole of `original`
+    fn original(original: &str, surface: &str, reading: &str) -> Vec<FuriganaSegment> {
+        let token = AnalyzedToken {
+            byte_start: 0,
+            byte_end: original.len(),
+            ..token(surface, "名詞,一般", "*", "*", surface, reading)
+        };
+        let sentence = AnalyzedSentence {
+            text: original.to_string(),
+            tokens: vec![token.clone()],
+            dependencies: Default::default(),
+            normalized: None,
+        };
+        original_furigana(&sentence, &token)
+    }
+
+    #[test]
+    fn original_text_is_shown() {
+        assert_eq!(
+            original("食べる", "食べる", "タベル"),
+            [segment("食", Some("た")), segment("べる", None)]
+        );
+        assert_eq!(
+            original("ＡＢＣ", "ABC", "エービーシー"),
+            [segment("ＡＢＣ", None)]
+        );
+        assert_eq!(
+            original("ﾒｰﾙ便", "メール便", "メールビン"),
+            [segment("ﾒｰﾙ", None), segment("便", Some("びん"))]
+        );
+        // ｶﾞ is two characters that normalize into one
+        assert_eq!(
+            original("ｶﾞｽ代", "ガス代", "ガスダイ"),
+            [segment("ｶﾞｽ", None), segment("代", Some("だい"))]
+        );
+        assert_eq!(
+            original("㍻", "平成", "ヘイセイ"),
+            [segment("㍻", Some("へいせい"))]
+        );
+    }
+
+    #[test]
+    fn untraceable_token_is_plain() {
+        // Part of what ㍻ expanded into
+        assert_eq!(original("㍻", "平", "ヘイ"), [segment("㍻", None)]);
+    }
+}
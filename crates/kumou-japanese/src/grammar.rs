@@ -0,0 +1,298 @@
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AnalysisError;
+use crate::tokenizer::{AnalyzedSentence, AnalyzedToken};
+
+const BUILTIN_RULES: &str = include_str!("../data/grammar_patterns.json");
+
+/// Conditions on one token of a grammar pattern
+///
+/// Each list holds the accepted values; an empty list accepts anything.
+/// POS values are a major category, optionally followed by subcategories
+/// separated by commas: `動詞` or `動詞,非自立`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct TokenPattern {
+    pub surface: Vec<String>,
+    pub base_form: Vec<String>,
+    pub pos: Vec<String>,
+    pub conjugation_form: Vec<String>,
+    /// The token may be left out
+    pub optional: bool,
+}
+
+impl TokenPattern {
+    pub fn matches(&self, token: &AnalyzedToken) -> bool {
+        let accepts = |values: &[String], value: &str| {
+            values.is_empty() || values.iter().any(|v| v == value)
+        };
+        accepts(&self.surface, &token.surface)
+            && accepts(&self.base_form, &token.base_form)
+            && accepts(&self.conjugation_form, token.conjugation_form.as_str())
+            && (self.pos.is_empty() || self.pos.iter().any(|pos| pos_matches(pos, token)))
+    }
+}
+
+/// A grammar point and the token sequence that expresses it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GrammarRule {
+    /// Stable identifier, e.g. `te-iru`
+    pub id: String,
+    /// Display name, e.g. ～ている
+    pub name: String,
+    pub explanation: String,
+    pub tokens: Vec<TokenPattern>,
+}
+
+/// A grammar pattern found in a sentence
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GrammarMatch {
+    pub id: String,
+    pub name: String,
+    pub explanation: String,
+    /// Index of the first matched token
+    pub start: usize,
+    /// Index one past the last matched token
+    pub end: usize,
+    /// Surface of the matched tokens
+    pub surface: String,
+}
+
+/// A set of grammar rules
+///
+/// Rules are written as JSON, see `data/grammar_patterns.json` for the
+/// built-in set:
+///
+/// ```text
+/// [{ "id": "te-kudasai", "name": "～てください", "explanation": "Polite request",
+///    "tokens": [{ "surface": ["て", "で"], "pos": ["助詞"] },
+///               { "base_form": ["くださる"] }] }]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GrammarRules {
+    pub rules: Vec<GrammarRule>,
+}
+
+impl GrammarRules {
+    /// Parse and validate rules from JSON text
+    pub fn parse(json: &str) -> Result<Self, AnalysisError> {
+        let rules: Vec<GrammarRule> = serde_json::from_str(json)
+            .map_err(|e| AnalysisError::GrammarRuleParse(e.to_string()))?;
+
+        for (index, rule) in rules.iter().enumerate() {
+            let invalid = |reason: &str| AnalysisError::GrammarRuleInvalid {
+                id: rule.id.clone(),
+                reason: reason.to_string(),
+            };
+            if rule.id.is_empty() {
+                return Err(invalid("id is empty"));
+            }
+            if rules[..index].iter().any(|r| r.id == rule.id) {
+                return Err(invalid("id is already used"));
+            }
+            if rule.tokens.iter().all(|t| t.optional) {
+                return Err(invalid("needs at least one required token"));
+            }
+        }
+        Ok(Self { rules })
+    }
+
+    /// The rules shipped with the crate
+    pub fn builtin() -> &'static GrammarRules {
+        static BUILTIN: OnceLock<GrammarRules> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            Self::parse(BUILTIN_RULES).expect("built-in grammar rules are valid")
+        })
+    }
+
+    /// Find every rule match in `sentence`, ordered by position
+    ///
+    /// Matches of different rules may overlap; a rule does not match again
+    /// inside its own earlier match.
+    pub fn find(&self, sentence: &AnalyzedSentence) -> Vec<GrammarMatch> {
+        let tokens = &sentence.tokens;
+        let mut matches = Vec::new();
+
+        for rule in &self.rules {
+            let mut start = 0;
+            while start < tokens.len() {
+                match match_at(&rule.tokens, tokens, start) {
+                    Some(end) => {
+                        matches.push(GrammarMatch {
+                            id: rule.id.clone(),
+                            name: rule.name.clone(),
+                            explanation: rule.explanation.clone(),
+                            start,
+                            end,
+                            surface: tokens[start..end]
+                                .iter()
+                                .map(|t| t.surface.as_str())
+                                .collect(),
+                        });
+                        start = end;
+                    }
+                    None => start += 1,
+                }
+            }
+        }
+
+        matches.sort_by_key(|m| (m.start, m.end));
+        matches
+    }
+}
+
+/// Detect the built-in grammar patterns in `sentence`
+pub fn detect_grammar(sentence: &AnalyzedSentence) -> Vec<GrammarMatch> {
+    GrammarRules::builtin().find(sentence)
+}
+
+/// End of a match of `patterns` starting at `start`, taking optional
+/// tokens whenever they fit
+fn match_at(patterns: &[TokenPattern], tokens: &[AnalyzedToken], start: usize) -> Option<usize> {
+    let Some((pattern, rest)) = patterns.split_first() else {
+        return Some(start);
+    };
+    let taken = tokens
+        .get(start)
+        .filter(|token| pattern.matches(token))
+        .and_then(|_| match_at(rest, tokens, start + 1));
+    match taken {
+        Some(end) => Some(end),
+        None if pattern.optional => match_at(rest, tokens, start),
+        None => None,
+    }
+}
+
+fn pos_matches(pattern: &str, token: &AnalyzedToken) -> bool {
+    let levels = [
+        token.pos.major.as_str(),
+        token.pos.sub1.as_str(),
+        token.pos.sub2.as_str(),
+        token.pos.sub3.as_str(),
+    ];
+    pattern
+        .split(',')
+        .zip(levels)
+        .all(|(expected, actual)| expected == actual)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sentence, token};
+
+    /// Id, start, end and surface of the built-in matches in `tokens`
+    fn found(tokens: Vec<AnalyzedToken>) -> Vec<(String, usize, usize, String)> {
+        detect_grammar(&sentence(tokens))
+            .into_iter()
+            .map(|m| (m.id, m.start, m.end, m.surface))
+            .collect()
+    }
+
+    fn hit(id: &str, start: usize, end: usize, surface: &str) -> (String, usize, usize, String) {
+        (id.to_string(), start, end, surface.to_string())
+    }
+
+    #[test]
+    fn builtin_rules_parse() {
+        let rules = GrammarRules::parse(BUILTIN_RULES).unwrap();
+        assert!(!rules.rules.is_empty());
+        assert_eq!(GrammarRules::builtin(), &rules);
+    }
+
+    #[test]
+    fn te_iru() {
+        let tabete_iru = vec![
+            token("食べ", "動詞,自立", "一段", "連用形", "食べる", "タベ"),
+            token("て", "助詞,接続助詞", "*", "*", "て", "テ"),
+            token("いる", "動詞,非自立", "一段", "基本形", "いる", "イル"),
+        ];
+        assert_eq!(found(tabete_iru), [hit("te-iru", 1, 3, "ている")]);
+
+        // The contraction てる is a single token
+        let tabeteru = vec![
+            token("食べ", "動詞,自立", "一段", "連用形", "食べる", "タベ"),
+            token("てる", "動詞,非自立", "一段", "基本形", "てる", "テル"),
+        ];
+        assert_eq!(found(tabeteru), [hit("te-iru", 1, 2, "てる")]);
+
+        // いる on its own is not ～ている
+        let iru = vec![token("いる", "動詞,自立", "一段", "基本形", "いる", "イル")];
+        assert_eq!(found(iru), []);
+    }
+
+    #[test]
+    fn te_mo_ii() {
+        let suwatte_mo_ii = vec![
+            token("座っ", "動詞,自立", "五段・ラ行", "連用タ接続", "座る", "スワッ"),
+            token("て", "助詞,接続助詞", "*", "*", "て", "テ"),
+            token("も", "助詞,係助詞", "*", "*", "も", "モ"),
+            token("いい", "形容詞,自立", "形容詞・イイ", "基本形", "いい", "イイ"),
+            token("です", "助動詞", "特殊・デス", "基本形", "です", "デス"),
+            token("か", "助詞,副助詞／並立助詞／終助詞", "*", "*", "か", "カ"),
+        ];
+        assert_eq!(found(suwatte_mo_ii), [hit("te-mo-ii", 1, 4, "てもいい")]);
+    }
+
+    #[test]
+    fn nakereba_naranai() {
+        let ikanakereba_naranai = vec![
+            token("行か", "動詞,自立", "五段・カ行促音便", "未然形", "行く", "イカ"),
+            token("なけれ", "助動詞", "特殊・ナイ", "仮定形", "ない", "ナケレ"),
+            token("ば", "助詞,接続助詞", "*", "*", "ば", "バ"),
+            token("なら", "動詞,自立", "五段・ラ行", "未然形", "なる", "ナラ"),
+            token("ない", "助動詞", "特殊・ナイ", "基本形", "ない", "ナイ"),
+        ];
+        assert_eq!(
+            found(ikanakereba_naranai),
+            [hit("nakereba-naranai", 1, 5, "なければならない")]
+        );
+
+        // Without the final negative it is only a condition
+        let ikanakereba_naru = vec![
+            token("行か", "動詞,自立", "五段・カ行促音便", "未然形", "行く", "イカ"),
+            token("なけれ", "助動詞", "特殊・ナイ", "仮定形", "ない", "ナケレ"),
+            token("ば", "助詞,接続助詞", "*", "*", "ば", "バ"),
+            token("なる", "動詞,自立", "五段・ラ行", "基本形", "なる", "ナル"),
+        ];
+        assert_eq!(found(ikanakereba_naru), []);
+    }
+
+    #[test]
+    fn pos_matches_by_prefix_of_levels() {
+        let iru = token("いる", "動詞,非自立", "一段", "基本形", "いる", "イル");
+        assert!(pos_matches("動詞", &iru));
+        assert!(pos_matches("動詞,非自立", &iru));
+        assert!(!pos_matches("動詞,自立", &iru));
+        assert!(!pos_matches("助動詞", &iru));
+    }
+
+    #[test]
+    fn invalid_rules() {
+        let error = |json: &str| GrammarRules::parse(json).unwrap_err();
+        assert!(matches!(error("{"), AnalysisError::GrammarRuleParse(_)));
+
+        let rule = |id: &str, optional: bool| {
+            format!(
+                r#"{{"id": "{id}", "name": "", "explanation": "",
+                    "tokens": [{{"surface": ["て"], "optional": {optional}}}]}}"#
+            )
+        };
+        let invalid = |json: String| match GrammarRules::parse(&json) {
+            Err(AnalysisError::GrammarRuleInvalid { reason, .. }) => reason,
+            other => panic!("{other:?}"),
+        };
+        assert_eq!(invalid(format!("[{}]", rule("", false))), "id is empty");
+        assert_eq!(
+            invalid(format!("[{}, {}]", rule("a", false), rule("a", false))),
+            "id is already used"
+        );
+        assert_eq!(
+            invalid(format!("[{}]", rule("a", true))),
+            "needs at least one required token"
+        );
+        assert!(GrammarRules::parse(&format!("[{}]", rule("a", false))).is_ok());
+    }
+}
@@ -0,0 +1,322 @@
+use serde::{Deserialize, Serialize};
+
+use crate::pos::{PosMajor, PosSub};
+use crate::tokenizer::{AnalyzedSentence, AnalyzedToken};
+
+/// A grammatical change applied to a predicate by a trailing morpheme
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Transformation {
+    /// させる, せる
+    Causative,
+    /// れる
+    Passive,
+    /// られる, which also marks the potential of ichidan verbs
+    PassivePotential,
+    /// ない, ぬ, ん
+    Negative,
+    /// た, だ
+    Past,
+    /// ます, です
+    Polite,
+    /// たい
+    Desire,
+    /// たがる
+    ShowsDesire,
+    /// う, よう
+    Volitional,
+    /// まい
+    NegativeVolitional,
+    /// て, で
+    TeForm,
+    /// ば, and the たら form of た
+    Conditional,
+    /// ながら
+    Simultaneous,
+    /// ～ている
+    Progressive,
+    /// ～てある
+    Resultative,
+    /// ～てしまう
+    Completion,
+    /// ～てみる
+    Attempt,
+    /// ～ておく
+    Preparation,
+    /// らしい
+    Hearsay,
+    /// Any other auxiliary
+    Other,
+}
+
+impl Transformation {
+    /// English label
+    pub fn label(&self) -> &'static str {
+        match self {
+            Transformation::Causative => "Causative",
+            Transformation::Passive => "Passive",
+            Transformation::PassivePotential => "Passive / Potential",
+            Transformation::Negative => "Negative",
+            Transformation::Past => "Past",
+            Transformation::Polite => "Polite",
+            Transformation::Desire => "Want to",
+            Transformation::ShowsDesire => "Shows desire",
+            Transformation::Volitional => "Volitional",
+            Transformation::NegativeVolitional => "Negative volitional",
+            Transformation::TeForm => "Te-form",
+            Transformation::Conditional => "Conditional",
+            Transformation::Simultaneous => "While doing",
+            Transformation::Progressive => "Progressive / State",
+            Transformation::Resultative => "Resultative",
+            Transformation::Completion => "Completion / Regret",
+            Transformation::Attempt => "Try doing",
+            Transformation::Preparation => "Do in advance",
+            Transformation::Hearsay => "Apparently",
+            Transformation::Other => "Auxiliary",
+        }
+    }
+}
+
+/// One trailing morpheme of a predicate and what it does
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InflectionStep {
+    pub surface: String,
+    pub base_form: String,
+    pub transformation: Transformation,
+}
+
+/// A predicate grouped with its trailing auxiliaries and particles
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InflectionChain {
+    /// Index of the predicate's first token in the sentence
+    pub start: usize,
+    /// Index one past the chain's last token
+    pub end: usize,
+    /// Surface of the whole chain, e.g. 食べさせられなかった
+    pub surface: String,
+    /// Dictionary form of the predicate, e.g. 食べる
+    pub lemma: String,
+    /// Transformations in the order they apply
+    pub steps: Vec<InflectionStep>,
+}
+
+/// Find verb and adjective phrases and explain how they were inflected
+///
+/// 食べさせられなかった gives 食べる with the steps causative → passive →
+/// negative → past. Predicates without any trailing inflection are skipped.
+pub fn inflection_chains(sentence: &AnalyzedSentence) -> Vec<InflectionChain> {
+    let tokens = &sentence.tokens;
+    let mut chains = Vec::new();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        if !is_predicate_head(&tokens[i]) {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let mut steps: Vec<InflectionStep> = Vec::new();
+        let mut end = i + 1;
+        while let Some(token) = tokens.get(end) {
+            let Some(transformation) = trailing_transformation(token) else {
+                break;
+            };
+            // ませんでした repeats the polite marker, once is enough
+            let repeated = transformation == Transformation::Polite
+                && steps.iter().any(|s| s.transformation == transformation);
+            if !repeated {
+                steps.push(InflectionStep {
+                    surface: token.surface.clone(),
+                    base_form: token.base_form.clone(),
+                    transformation,
+                });
+            }
+            end += 1;
+        }
+
+        if !steps.is_empty() {
+            let head = &tokens[start];
+            chains.push(InflectionChain {
+                start,
+                end,
+                surface: tokens[start..end].iter().map(|t| t.surface.as_str()).collect(),
+                lemma: if head.base_form == "*" {
+                    head.surface.clone()
+                } else {
+                    head.base_form.clone()
+                },
+                steps,
+            });
+        }
+        i = end;
+    }
+
+    chains
+}
+
+fn is_predicate_head(token: &AnalyzedToken) -> bool {
+    matches!(token.pos.major, PosMajor::Verb | PosMajor::Adjective)
+        && token.pos.sub1 == PosSub::Independent
+}
+
+/// What `token` does to the predicate it follows, if it belongs to the
+/// predicate at all
+fn trailing_transformation(token: &AnalyzedToken) -> Option<Transformation> {
+    let base = token.base_form.as_str();
+
+    match (&token.pos.major, &token.pos.sub1) {
+        (PosMajor::Verb, PosSub::Suffix) => Some(match base {
+            "させる" | "せる" => Transformation::Causative,
+            "れる" => Transformation::Passive,
+            "られる" => Transformation::PassivePotential,
+            "たがる" => Transformation::ShowsDesire,
+            _ => Transformation::Other,
+        }),
+        (PosMajor::Verb, PosSub::Dependent) => Some(match base {
+            "いる" => Transformation::Progressive,
+            "ある" => Transformation::Resultative,
+            "しまう" | "ちゃう" | "じゃう" => Transformation::Completion,
+            "みる" => Transformation::Attempt,
+            "おく" | "とく" => Transformation::Preparation,
+            _ => Transformation::Other,
+        }),
+        // 高くない, 食べてない: ない is tagged as an adjective here
+        (PosMajor::Adjective, _) if base == "ない" => Some(Transformation::Negative),
+        (PosMajor::AuxiliaryVerb, _) => Some(match base {
+            "ない" | "ぬ" | "ん" => Transformation::Negative,
+            "た" | "だ" if matches!(token.surface.as_str(), "たら" | "だら") => {
+                Transformation::Conditional
+            }
+            "た" | "だ" => Transformation::Past,
+            "ます" | "です" => Transformation::Polite,
+            "たい" => Transformation::Desire,
+            "う" | "よう" => Transformation::Volitional,
+            "まい" => Transformation::NegativeVolitional,
+            "らしい" => Transformation::Hearsay,
+            _ => Transformation::Other,
+        }),
+        (PosMajor::Particle, PosSub::ConjunctiveParticle) => match token.surface.as_str() {
+            "て" | "で" => Some(Transformation::TeForm),
+            "ば" => Some(Transformation::Conditional),
+            "ながら" => Some(Transformation::Simultaneous),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sentence, token};
+
+    fn t(surface: &str, pos: &str, form: &str, base: &str) -> AnalyzedToken {
+        token(surface, pos, "*", form, base, "*")
+    }
+
+    fn transformations(chain: &InflectionChain) -> Vec<Transformation> {
+        chain.steps.iter().map(|s| s.transformation).collect()
+    }
+
+    #[test]
+    fn causative_passive_negative_past() {
+        let chains = inflection_chains(&sentence(vec![
+            t("食べ", "動詞,自立", "未然形", "食べる"),
+            t("させ", "動詞,接尾", "未然形", "させる"),
+            t("られ", "動詞,接尾", "未然形", "られる"),
+            t("なかっ", "助動詞", "連用タ接続", "ない"),
+            t("た", "助動詞", "基本形", "た"),
+            t("。", "記号,句点", "*", "。"),
+        ]));
+        assert_eq!(chains.len(), 1);
+        let chain = &chains[0];
+        assert_eq!((chain.start, chain.end), (0, 5));
+        assert_eq!(chain.surface, "食べさせられなかった");
+        assert_eq!(chain.lemma, "食べる");
+        assert_eq!(
+            transformations(chain),
+            [
+                Transformation::Causative,
+                Transformation::PassivePotential,
+                Transformation::Negative,
+                Transformation::Past
+            ]
+        );
+        let surfaces: Vec<&str> = chain.steps.iter().map(|s| s.surface.as_str()).collect();
+        assert_eq!(surfaces, ["させ", "られ", "なかっ", "た"]);
+    }
+
+    #[test]
+    fn polite_marker_counts_once() {
+        let chains = inflection_chains(&sentence(vec![
+            t("行き", "動詞,自立", "連用形", "行く"),
+            t("ませ", "助動詞", "未然形", "ます"),
+            t("ん", "助動詞", "基本形", "ん"),
+            t("でし", "助動詞", "連用形", "です"),
+            t("た", "助動詞", "基本形", "た"),
+        ]));
+        assert_eq!(
+            transformations(&chains[0]),
+            [
+                Transformation::Polite,
+                Transformation::Negative,
+                Transformation::Past
+            ]
+        );
+    }
+
+    #[test]
+    fn several_chains() {
+        // 本を読んでいたら、高くない
+        let chains = inflection_chains(&sentence(vec![
+            t("本", "名詞,一般", "*", "本"),
+            t("を", "助詞,格助詞", "*", "を"),
+            t("読ん", "動詞,自立", "連用タ接続", "読む"),
+            t("で", "助詞,接続助詞", "*", "で"),
+            t("い", "動詞,非自立", "連用形", "いる"),
+            t("たら", "助動詞", "仮定形", "た"),
+            t("、", "記号,読点", "*", "、"),
+            t("高く", "形容詞,自立", "連用テ接続", "高い"),
+            t("ない", "形容詞,非自立", "基本形", "ない"),
+        ]));
+        let found: Vec<(usize, usize, &str)> = chains
+            .iter()
+            .map(|c| (c.start, c.end, c.lemma.as_str()))
+            .collect();
+        assert_eq!(found, [(2, 6, "読む"), (7, 9, "高い")]);
+        assert_eq!(
+            transformations(&chains[0]),
+            [
+                Transformation::TeForm,
+                Transformation::Progressive,
+                Transformation::Conditional
+            ]
+        );
+        assert_eq!(transformations(&chains[1]), [Transformation::Negative]);
+    }
+
+    #[test]
+    fn uninflected_predicates_are_skipped() {
+        let chains = inflection_chains(&sentence(vec![
+            t("食べる", "動詞,自立", "基本形", "食べる"),
+            t("。", "記号,句点", "*", "。"),
+        ]));
+        assert!(chains.is_empty());
+    }
+
+    #[test]
+    fn trailing_morphemes() {
+        let cases = [
+            (t("たがる", "動詞,接尾", "基本形", "たがる"), Some(Transformation::ShowsDesire)),
+            (t("しまう", "動詞,非自立", "基本形", "しまう"), Some(Transformation::Completion)),
+            (t("よう", "助動詞", "基本形", "よう"), Some(Transformation::Volitional)),
+            (t("そうだ", "助動詞", "基本形", "そうだ"), Some(Transformation::Other)),
+            (t("ば", "助詞,接続助詞", "*", "ば"), Some(Transformation::Conditional)),
+            (t("から", "助詞,接続助詞", "*", "から"), None),
+            (t("を", "助詞,格助詞", "*", "を"), None),
+        ];
+        for (token, expected) in cases {
+            assert_eq!(trailing_transformation(&token), expected, "{}", token.surface);
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AnalysisError;
+use crate::kana::is_kanji;
+use crate::pos::{PosMajor, PosSub};
+use crate::tokenizer::{AnalyzedSentence, AnalyzedToken};
+
+/// JLPT level, from N5 (easiest) to N1 (hardest)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JlptLevel {
+    N5,
+    N4,
+    N3,
+    N2,
+    N1,
+}
+
+impl JlptLevel {
+    pub const ALL: [JlptLevel; 5] = [
+        JlptLevel::N5,
+        JlptLevel::N4,
+        JlptLevel::N3,
+        JlptLevel::N2,
+        JlptLevel::N1,
+    ];
+
+    /// Name used in lists and labels, e.g. `N5`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JlptLevel::N5 => "N5",
+            JlptLevel::N4 => "N4",
+            JlptLevel::N3 => "N3",
+            JlptLevel::N2 => "N2",
+            JlptLevel::N1 => "N1",
+        }
+    }
+
+    /// CSS class for coloring by level, e.g. `jlpt-n5`
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            JlptLevel::N5 => "jlpt-n5",
+            JlptLevel::N4 => "jlpt-n4",
+            JlptLevel::N3 => "jlpt-n3",
+            JlptLevel::N2 => "jlpt-n2",
+            JlptLevel::N1 => "jlpt-n1",
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl FromStr for JlptLevel {
+    type Err = ();
+
+    /// Accepts `N5`, `n5` or `5`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let digit = s.strip_prefix(['N', 'n']).unwrap_or(s);
+        match digit {
+            "5" => Ok(JlptLevel::N5),
+            "4" => Ok(JlptLevel::N4),
+            "3" => Ok(JlptLevel::N3),
+            "2" => Ok(JlptLevel::N2),
+            "1" => Ok(JlptLevel::N1),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Number of distinct items at each level
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LevelCounts {
+    /// Counts indexed in [`JlptLevel::ALL`] order, N5 first
+    pub levels: [usize; 5],
+    /// Items not found in any list
+    pub unlisted: usize,
+}
+
+impl LevelCounts {
+    pub fn get(&self, level: JlptLevel) -> usize {
+        self.levels[level.index()]
+    }
+
+    pub fn total(&self) -> usize {
+        self.levels.iter().sum::<usize>() + self.unlisted
+    }
+
+    fn add(&mut self, level: Option<JlptLevel>) {
+        match level {
+            Some(level) => self.levels[level.index()] += 1,
+            None => self.unlisted += 1,
+        }
+    }
+}
+
+/// JLPT levels of the distinct words and kanji in a text
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct JlptBreakdown {
+    pub vocabulary: LevelCounts,
+    pub kanji: LevelCounts,
+}
+
+/// JLPT vocabulary and kanji lists
+///
+/// Both lists are CSV with one entry per line, the item and its level:
+///
+/// ```text
+/// # word,level
+/// 食べる,N5
+/// 経験,N3
+/// ```
+///
+/// Levels may be written `N5` or `5`. Kanji lists hold one kanji per line.
+/// An item listed at several levels keeps the easiest. Blank lines and
+/// lines starting with `#` are ignored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JlptLists {
+    vocabulary: HashMap<String, JlptLevel>,
+    kanji: HashMap<char, JlptLevel>,
+}
+
+impl JlptLists {
+    /// Parse and validate vocabulary and kanji lists from CSV text
+    pub fn parse(vocabulary: &str, kanji: &str) -> Result<Self, AnalysisError> {
+        let mut lists = Self::default();
+        for (line, word, level) in parse_list(vocabulary)? {
+            if word.is_empty() {
+                return Err(AnalysisError::JlptEntry {
+                    line,
+                    entry: word.to_string(),
+                });
+            }
+            insert_easiest(&mut lists.vocabulary, word.to_string(), level);
+        }
+        for (line, entry, level) in parse_list(kanji)? {
+            let mut chars = entry.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if is_kanji(c) => insert_easiest(&mut lists.kanji, c, level),
+                _ => {
+                    return Err(AnalysisError::JlptEntry {
+                        line,
+                        entry: entry.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(lists)
+    }
+
+    /// Read and validate list files; either may be left out
+    pub fn load(vocabulary: Option<&Path>, kanji: Option<&Path>) -> Result<Self, AnalysisError> {
+        let read = |path: Option<&Path>| match path {
+            Some(path) => std::fs::read_to_string(path)
+                .map_err(|e| AnalysisError::JlptListRead(format!("{}: {e}", path.display()))),
+            None => Ok(String::new()),
+        };
+        Self::parse(&read(vocabulary)?, &read(kanji)?)
+    }
+
+    /// Level of a word in its dictionary form
+    pub fn word_level(&self, word: &str) -> Option<JlptLevel> {
+        self.vocabulary.get(word).copied()
+    }
+
+    /// Level of a single kanji
+    pub fn kanji_level(&self, kanji: char) -> Option<JlptLevel> {
+        self.kanji.get(&kanji).copied()
+    }
+
+    /// Level of the token's dictionary form
+    pub fn token_level(&self, token: &AnalyzedToken) -> Option<JlptLevel> {
+        self.word_level(token_lemma(token))
+    }
+
+    /// Fill in the level of every token found in the vocabulary list
+    pub fn annotate(&self, sentence: &mut AnalyzedSentence) {
+        for token in &mut sentence.tokens {
+            token.jlpt = self.token_level(token);
+        }
+    }
+
+    /// Count the distinct content words and kanji of `sentences` by level
+    ///
+    /// Particles, auxiliaries, symbols, numbers and dependent words such as
+    /// the いる of ～ている are left out of the vocabulary.
+    pub fn breakdown(&self, sentences: &[AnalyzedSentence]) -> JlptBreakdown {
+        let words: BTreeSet<&str> = sentences
+            .iter()
+            .flat_map(|s| &s.tokens)
+            .filter(|t| is_vocabulary(t))
+            .map(token_lemma)
+            .collect();
+        let kanji: BTreeSet<char> = sentences
+            .iter()
+            .flat_map(|s| s.text.chars())
+            .filter(|&c| is_kanji(c))
+            .collect();
+
+        let mut breakdown = JlptBreakdown::default();
+        for word in words {
+            breakdown.vocabulary.add(self.word_level(word));
+        }
+        for c in kanji {
+            breakdown.kanji.add(self.kanji_level(c));
+        }
+        breakdown
+    }
+}
+
+/// Non-comment lines split into line number, item and level
+fn parse_list(csv: &str) -> Result<Vec<(usize, &str, JlptLevel)>, AnalysisError> {
+    let mut entries = Vec::new();
+    for (index, raw) in csv.lines().enumerate() {
+        let line = index + 1;
+        let raw = raw.trim();
+        if raw.is_empty() || raw.starts_with('#') {
+            continue;
+        }
+
+        let columns: Vec<&str> = raw.split(',').map(str::trim).collect();
+        let [item, level] = columns[..] else {
+            return Err(AnalysisError::JlptColumns {
+                line,
+                found: columns.len(),
+            });
+        };
+        let level = level.parse().map_err(|_| AnalysisError::JlptLevel {
+            line,
+            level: level.to_string(),
+        })?;
+        entries.push((line, item, level));
+    }
+    Ok(entries)
+}
+
+fn insert_easiest<K: std::hash::Hash + Eq>(
+    map: &mut HashMap<K, JlptLevel>,
+    key: K,
+    level: JlptLevel,
+) {
+    map.entry(key)
+        .and_modify(|existing| *existing = (*existing).min(level))
+        .or_insert(level);
+}
+
+fn token_lemma(token: &AnalyzedToken) -> &str {
+    if token.base_form == "*" {
+        &token.surface
+    } else {
+        &token.base_form
+    }
+}
+
+fn is_vocabulary(token: &AnalyzedToken) -> bool {
+    !matches!(
+        token.pos.major,
+        PosMajor::Particle
+            | PosMajor::AuxiliaryVerb
+            | PosMajor::Symbol
+            | PosMajor::SupplementarySymbol
+            | PosMajor::Filler
+            | PosMajor::Miscellaneous
+    ) && !matches!(token.pos.sub1, PosSub::Number | PosSub::Dependent | PosSub::Suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sentence, token};
+
+    const VOCABULARY: &str = "# word,level\n食べる,N5\n\n経験, 3\n学生,n5\n経験,N2\n";
+    const KANJI: &str = "食,N5\n験,N3\n";
+
+    #[test]
+    fn parse_lists() {
+        let lists = JlptLists::parse(VOCABULARY, KANJI).unwrap();
+        assert_eq!(lists.word_level("食べる"), Some(JlptLevel::N5));
+        assert_eq!(lists.word_level("学生"), Some(JlptLevel::N5));
+        // Listed twice, the easier level is kept
+        assert_eq!(lists.word_level("経験"), Some(JlptLevel::N3));
+        assert_eq!(lists.word_level("飲む"), None);
+        assert_eq!(lists.kanji_level('食'), Some(JlptLevel::N5));
+        assert_eq!(lists.kanji_level('飲'), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(
+            JlptLists::parse("食べる", ""),
+            Err(AnalysisError::JlptColumns { line: 1, found: 1 })
+        ));
+        assert!(matches!(
+            JlptLists::parse("# comment\n食べる,N5,verb", ""),
+            Err(AnalysisError::JlptColumns { line: 2, found: 3 })
+        ));
+        assert!(matches!(
+            JlptLists::parse("食べる,N6", ""),
+            Err(AnalysisError::JlptLevel { line: 1, level }) if level == "N6"
+        ));
+        assert!(matches!(
+            JlptLists::parse(",N5", ""),
+            Err(AnalysisError::JlptEntry { line: 1, .. })
+        ));
+        for entry in ["食べ", "あ", ""] {
+            assert!(
+                matches!(
+                    JlptLists::parse("", &format!("{entry},N5")),
+                    Err(AnalysisError::JlptEntry { line: 1, .. })
+                ),
+                "{entry}"
+            );
+        }
+    }
+
+    /// 学生が経験を食べた, with 学生 and 経験 as words
+    fn gakusei_ga_keiken() -> AnalyzedSentence {
+        sentence(vec![
+            token("学生", "名詞,一般", "*", "*", "学生", "ガクセイ"),
+            token("が", "助詞,格助詞", "*", "*", "が", "ガ"),
+            token("経験", "名詞,サ変接続", "*", "*", "経験", "ケイケン"),
+            token("を", "助詞,格助詞", "*", "*", "を", "ヲ"),
+            token("食べ", "動詞,自立", "一段", "連用形", "食べる", "タベ"),
+            token("た", "助動詞", "特殊・タ", "基本形", "た", "タ"),
+        ])
+    }
+
+    #[test]
+    fn annotate_by_base_form() {
+        let lists = JlptLists::parse(VOCABULARY, KANJI).unwrap();
+        let mut sentence = gakusei_ga_keiken();
+        lists.annotate(&mut sentence);
+        let levels: Vec<Option<JlptLevel>> = sentence.tokens.iter().map(|t| t.jlpt).collect();
+        assert_eq!(
+            levels,
+            [
+                Some(JlptLevel::N5),
+                None,
+                Some(JlptLevel::N3),
+                None,
+                Some(JlptLevel::N5),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn breakdown_counts_distinct_items() {
+        let lists = JlptLists::parse(VOCABULARY, KANJI).unwrap();
+        let breakdown = lists.breakdown(&[gakusei_ga_keiken(), gakusei_ga_keiken()]);
+
+        // 学生, 経験 and 食べる; particles and auxiliaries are left out
+        assert_eq!(breakdown.vocabulary.get(JlptLevel::N5), 2);
+        assert_eq!(breakdown.vocabulary.get(JlptLevel::N3), 1);
+        assert_eq!(breakdown.vocabulary.unlisted, 0);
+        assert_eq!(breakdown.vocabulary.total(), 3);
+
+        // 学, 生, 経, 験 and 食
+        assert_eq!(breakdown.kanji.get(JlptLevel::N5), 1);
+        assert_eq!(breakdown.kanji.get(JlptLevel::N3), 1);
+        assert_eq!(breakdown.kanji.unlisted, 3);
+        assert_eq!(breakdown.kanji.total(), 5);
+    }
+
+    #[test]
+    fn level_from_str() {
+        for (text, level) in [("N5", JlptLevel::N5), ("n4", JlptLevel::N4), (" 1 ", JlptLevel::N1)] {
+            assert_eq!(text.parse(), Ok(level), "{text}");
+        }
+        assert_eq!("N0".parse::<JlptLevel>(), Err(()));
+    }
+}
@@ -0,0 +1,446 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use roxmltree::{Document, Node, ParsingOptions};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AnalysisError;
+use crate::kana::katakana_to_hiragana;
+use crate::pos::PosMajor;
+use crate::tokenizer::{AnalyzedSentence, AnalyzedToken};
+
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// Most senses kept per token when annotating
+const MAX_TOKEN_SENSES: usize = 3;
+
+/// One meaning of a JMdict entry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JmdictSense {
+    /// Parts of speech as described by JMdict, e.g. "Ichidan verb"
+    pub pos: Vec<String>,
+    /// English glosses
+    pub glosses: Vec<String>,
+}
+
+/// A JMdict entry: the kanji and kana spellings of a word and its senses
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JmdictEntry {
+    /// Entry sequence number (ent_seq)
+    pub id: u32,
+    /// Kanji spellings (keb), most common first
+    pub kanji: Vec<String>,
+    /// Kana readings (reb), most common first
+    pub readings: Vec<String>,
+    pub senses: Vec<JmdictSense>,
+    /// Whether any spelling carries a news1, ichi1, spec or gai1 priority
+    pub common: bool,
+}
+
+/// JMdict lookup indexed by kanji and kana spelling
+///
+/// The XML is read once into a compact form and then dropped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Jmdict {
+    entries: Vec<JmdictEntry>,
+    index: HashMap<String, Vec<usize>>,
+}
+
+impl Jmdict {
+    /// Parse JMdict XML text
+    pub fn parse(xml: &str) -> Result<Self, AnalysisError> {
+        let options = ParsingOptions {
+            allow_dtd: true,
+            ..ParsingOptions::default()
+        };
+        let document = Document::parse_with_options(xml, options)
+            .map_err(|e| AnalysisError::JmdictParse(e.to_string()))?;
+
+        let mut dictionary = Self::default();
+        for node in document.root_element().children() {
+            if node.has_tag_name("entry") {
+                dictionary.insert(parse_entry(node));
+            }
+        }
+        Ok(dictionary)
+    }
+
+    /// Read and parse a JMdict XML file
+    pub fn load(path: &Path) -> Result<Self, AnalysisError> {
+        let xml = std::fs::read_to_string(path)
+            .map_err(|e| AnalysisError::JmdictRead(format!("{}: {e}", path.display())))?;
+        Self::parse(&xml)
+    }
+
+    fn insert(&mut self, entry: JmdictEntry) {
+        let position = self.entries.len();
+        for form in entry.kanji.iter().chain(&entry.readings) {
+            let positions = self.index.entry(form.clone()).or_default();
+            if !positions.contains(&position) {
+                positions.push(position);
+            }
+        }
+        self.entries.push(entry);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries spelled `form` in kanji or kana, in dictionary order
+    pub fn lookup(&self, form: &str) -> Vec<&JmdictEntry> {
+        self.index
+            .get(form)
+            .map(|positions| positions.iter().map(|&i| &self.entries[i]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Entries for the token's dictionary form, best match first
+    ///
+    /// Entries score for a reading that matches the token's, for a sense
+    /// whose part of speech fits the token's, and for being common.
+    pub fn token_entries(&self, token: &AnalyzedToken) -> Vec<&JmdictEntry> {
+        let lemma = if token.base_form == "*" {
+            &token.surface
+        } else {
+            &token.base_form
+        };
+        // The token reading is of the surface, so it only applies uninflected
+        let reading = match &token.lexeme {
+            Some(lexeme) => Some(katakana_to_hiragana(&lexeme.lemma_reading)),
+            None if lemma == &token.surface && token.reading != "*" => {
+                Some(katakana_to_hiragana(&token.reading))
+            }
+            None => None,
+        };
+
+        let mut scored: Vec<(usize, &JmdictEntry)> = self
+            .lookup(lemma)
+            .into_iter()
+            .map(|entry| {
+                let reading_match = reading
+                    .as_ref()
+                    .is_some_and(|r| entry.readings.iter().any(|e| e == r));
+                let pos_match = entry
+                    .senses
+                    .iter()
+                    .any(|s| sense_fits(s, &token.pos.major));
+                let score =
+                    4 * reading_match as usize + 2 * pos_match as usize + entry.common as usize;
+                (score, entry)
+            })
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// Glosses of the best entry for `token`, one string per sense
+    ///
+    /// Senses whose part of speech fits the token come first; at most
+    /// three are returned.
+    pub fn token_glosses(&self, token: &AnalyzedToken) -> Vec<String> {
+        let Some(entry) = self.token_entries(token).into_iter().next() else {
+            return Vec::new();
+        };
+        let (mut fitting, other): (Vec<&JmdictSense>, Vec<&JmdictSense>) = entry
+            .senses
+            .iter()
+            .partition(|s| sense_fits(s, &token.pos.major));
+        fitting.extend(other);
+        fitting
+            .into_iter()
+            .filter(|s| !s.glosses.is_empty())
+            .take(MAX_TOKEN_SENSES)
+            .map(|s| s.glosses.join("; "))
+            .collect()
+    }
+
+    /// Fill in the glosses of every token found in the dictionary
+    pub fn annotate(&self, sentence: &mut AnalyzedSentence) {
+        for token in &mut sentence.tokens {
+            token.glosses = self.token_glosses(token);
+        }
+    }
+}
+
+fn parse_entry(node: Node) -> JmdictEntry {
+    let mut entry = JmdictEntry {
+        id: 0,
+        kanji: Vec::new(),
+        readings: Vec::new(),
+        senses: Vec::new(),
+        common: false,
+    };
+    // Senses without their own POS share the previous sense's
+    let mut last_pos: Vec<String> = Vec::new();
+
+    for child in node.children().filter(|n| n.is_element()) {
+        match child.tag_name().name() {
+            "ent_seq" => entry.id = child.text().and_then(|t| t.parse().ok()).unwrap_or(0),
+            "k_ele" | "r_ele" => {
+                let is_kanji = child.has_tag_name("k_ele");
+                let (form_tag, priority_tag) =
+                    if is_kanji { ("keb", "ke_pri") } else { ("reb", "re_pri") };
+                let Some(form) = child_texts(child, form_tag).into_iter().next() else {
+                    continue;
+                };
+                entry.common |= child_texts(child, priority_tag)
+                    .iter()
+                    .any(|p| matches!(p.as_str(), "news1" | "ichi1" | "spec1" | "spec2" | "gai1"));
+                if is_kanji {
+                    entry.kanji.push(form);
+                } else {
+                    entry.readings.push(form);
+                }
+            }
+            "sense" => {
+                let pos = child_texts(child, "pos");
+                if !pos.is_empty() {
+                    last_pos = pos;
+                }
+                let glosses = child
+                    .children()
+                    .filter(|n| n.has_tag_name("gloss"))
+                    .filter(|n| {
+                        matches!(n.attribute((XML_NAMESPACE, "lang")), None | Some("eng"))
+                    })
+                    .filter_map(|n| n.text().map(str::to_string))
+                    .collect();
+                entry.senses.push(JmdictSense {
+                    pos: last_pos.clone(),
+                    glosses,
+                });
+            }
+            _ => {}
+        }
+    }
+    entry
+}
+
+fn child_texts(node: Node, name: &str) -> Vec<String> {
+    node.children()
+        .filter(|n| n.has_tag_name(name))
+        .filter_map(|n| n.text().map(str::to_string))
+        .collect()
+}
+
+/// Whether a sense's JMdict POS fits an IPADIC/UniDic major POS
+///
+/// Works with both the entity names ("v1") and the descriptions they expand
+/// to ("Ichidan verb"), which are mapped back to entity names first.
+fn sense_fits(sense: &JmdictSense, major: &PosMajor) -> bool {
+    sense.pos.iter().any(|pos| code_fits(pos_code(pos), major))
+}
+
+/// Entity name of a JMdict POS, e.g. `adj-i` for "adjective (keiyoushi)";
+/// `v` stands for any verb description and unknown ones give `""`
+fn pos_code(pos: &str) -> &str {
+    let description = pos.trim().to_ascii_lowercase();
+    let starts = |text: &str| description.starts_with(text);
+    let has = |text: &str| description.contains(text);
+    match description.as_str() {
+        "particle" => "prt",
+        "conjunction" => "conj",
+        "prefix" => "pref",
+        "suffix" => "suf",
+        "pronoun" => "pn",
+        "counter" => "ctr",
+        "numeric" => "num",
+        "copula" => "cop",
+        "auxiliary" => "aux",
+        _ if !has(" ") => pos.trim(),
+        _ if has("takes the aux. verb suru") => "vs",
+        _ if starts("auxiliary") => "aux",
+        _ if has("(keiyodoshi)") => "adj-na",
+        _ if has("(keiyoushi)") => "adj-i",
+        _ if has("(rentaishi)") => "adj-pn",
+        _ if starts("adverbial noun") => "n-adv",
+        _ if starts("adverb") => "adv",
+        _ if starts("noun, used as a suffix") => "n-suf",
+        _ if starts("noun, used as a prefix") => "n-pref",
+        _ if starts("noun") || starts("nouns which") => "n",
+        _ if starts("interjection") => "int",
+        _ if has(" verb") => "v",
+        _ => "",
+    }
+}
+
+/// Whether a JMdict POS entity name fits an IPADIC/UniDic major POS
+fn code_fits(code: &str, major: &PosMajor) -> bool {
+    match major {
+        // IPADIC files な-adjective stems and サ変 nouns under 名詞
+        PosMajor::Noun | PosMajor::Pronoun => matches!(
+            code,
+            "n" | "n-adv" | "n-t" | "n-pr" | "n-suf" | "n-pref" | "pn" | "num" | "ctr" | "vs"
+                | "adj-na" | "adj-no"
+        ),
+        // vs is the noun that takes する, the verbs are vs-i and vs-s
+        PosMajor::Verb => code.starts_with('v') && code != "vs",
+        PosMajor::Adjective => matches!(code, "adj-i" | "adj-ix"),
+        PosMajor::AdjectivalNoun | PosMajor::AdjectivalVerb => code == "adj-na",
+        PosMajor::Adverb => matches!(code, "adv" | "adv-to" | "n-adv"),
+        PosMajor::Particle => code == "prt",
+        PosMajor::AuxiliaryVerb => matches!(code, "aux" | "aux-v" | "aux-adj" | "cop"),
+        PosMajor::Conjunction => code == "conj",
+        PosMajor::Interjection | PosMajor::Filler => code == "int",
+        PosMajor::Adnominal => code == "adj-pn",
+        PosMajor::Prefix | PosMajor::UniDicPrefix => matches!(code, "pref" | "n-pref"),
+        PosMajor::Suffix => matches!(code, "suf" | "n-suf"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::token;
+
+    const JMDICT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE JMdict [
+<!ELEMENT JMdict (entry*)>
+<!ENTITY adj-i "adjective (keiyoushi)">
+<!ENTITY adj-na "adjectival nouns or quasi-adjectives (keiyodoshi)">
+<!ENTITY adj-no "nouns which may take the genitive case particle 'no'">
+<!ENTITY n "noun (common) (futsuumeishi)">
+<!ENTITY pref "prefix">
+<!ENTITY prt "particle">
+]>
+<JMdict>
+<entry>
+<ent_seq>1080510</ent_seq>
+<k_ele><keb>上手</keb><ke_pri>ichi1</ke_pri><ke_pri>news1</ke_pri></k_ele>
+<r_ele><reb>じょうず</reb><re_pri>ichi1</re_pri></r_ele>
+<sense><pos>&adj-na;</pos><pos>&n;</pos><gloss>skillful</gloss><gloss>skilled</gloss><gloss xml:lang="fre">habile</gloss></sense>
+<sense><pos>&n;</pos><gloss>flattery</gloss></sense>
+</entry>
+<entry>
+<ent_seq>1352270</ent_seq>
+<k_ele><keb>上手</keb></k_ele>
+<r_ele><reb>かみて</reb></r_ele>
+<sense><pos>&n;</pos><gloss>upper stream</gloss></sense>
+<sense><gloss>stage left</gloss></sense>
+</entry>
+<entry>
+<ent_seq>1352280</ent_seq>
+<k_ele><keb>上手い</keb><ke_pri>ichi1</ke_pri></k_ele>
+<r_ele><reb>うまい</reb><re_pri>ichi1</re_pri></r_ele>
+<sense><pos>&adj-i;</pos><gloss>skillful</gloss><gloss>clever</gloss></sense>
+</entry>
+<entry>
+<ent_seq>1250090</ent_seq>
+<k_ele><keb>空</keb><ke_pri>ichi1</ke_pri></k_ele>
+<r_ele><reb>から</reb><re_pri>ichi1</re_pri></r_ele>
+<sense><pos>&adj-no;</pos><pos>&n;</pos><gloss>empty</gloss><gloss>vacant</gloss></sense>
+<sense><pos>&pref;</pos><gloss>false</gloss><gloss>pretend</gloss></sense>
+</entry>
+<entry>
+<ent_seq>1002980</ent_seq>
+<r_ele><reb>から</reb><re_pri>spec1</re_pri></r_ele>
+<sense><pos>&prt;</pos><gloss>from</gloss></sense>
+<sense><gloss>because</gloss></sense>
+</entry>
+</JMdict>
+"#;
+
+    fn dictionary() -> Jmdict {
+        Jmdict::parse(JMDICT).unwrap()
+    }
+
+    fn best_id(token: &AnalyzedToken) -> Option<u32> {
+        dictionary().token_entries(token).first().map(|e| e.id)
+    }
+
+    #[test]
+    fn parse_entries() {
+        let dictionary = dictionary();
+        assert_eq!(dictionary.len(), 5);
+        let ids: Vec<u32> = dictionary.lookup("上手").iter().map(|e| e.id).collect();
+        assert_eq!(ids, [1080510, 1352270]);
+        let ids: Vec<u32> = dictionary.lookup("から").iter().map(|e| e.id).collect();
+        assert_eq!(ids, [1250090, 1002980]);
+
+        let jouzu = dictionary.lookup("じょうず")[0];
+        assert!(jouzu.common);
+        // The French gloss is left out, entities are expanded
+        assert_eq!(jouzu.senses[0].glosses, ["skillful", "skilled"]);
+        assert_eq!(jouzu.senses[0].pos[0], "adjectival nouns or quasi-adjectives (keiyodoshi)");
+
+        // A sense without POS keeps the previous sense's
+        let kamite = dictionary.lookup("かみて")[0];
+        assert!(!kamite.common);
+        assert_eq!(kamite.senses[1].pos, kamite.senses[0].pos);
+    }
+
+    #[test]
+    fn homographs_by_reading() {
+        let jouzu = token("上手", "名詞,形容動詞語幹", "*", "*", "上手", "ジョウズ");
+        assert_eq!(best_id(&jouzu), Some(1080510));
+        let kamite = token("上手", "名詞,一般", "*", "*", "上手", "カミテ");
+        assert_eq!(best_id(&kamite), Some(1352270));
+        // Inflected tokens are looked up by their lemma
+        let umaku = token("上手く", "形容詞,自立", "形容詞・アウオ段", "連用テ接続", "上手い", "ウマク");
+        assert_eq!(best_id(&umaku), Some(1352280));
+    }
+
+    #[test]
+    fn homographs_by_part_of_speech() {
+        // Both から entries match the reading, the particle fits the token
+        let kara = token("から", "助詞,格助詞", "*", "*", "から", "カラ");
+        assert_eq!(best_id(&kara), Some(1002980));
+        let empty = token("から", "名詞,一般", "*", "*", "から", "カラ");
+        assert_eq!(best_id(&empty), Some(1250090));
+    }
+
+    #[test]
+    fn fitting_senses_first() {
+        let dictionary = dictionary();
+        // 空 as in 空元気
+        let prefix = token("空", "接頭詞,名詞接続", "*", "*", "空", "カラ");
+        assert_eq!(dictionary.token_glosses(&prefix), ["false; pretend", "empty; vacant"]);
+        let noun = token("空", "名詞,一般", "*", "*", "空", "カラ");
+        assert_eq!(dictionary.token_glosses(&noun), ["empty; vacant", "false; pretend"]);
+
+        let unknown = token("下手", "名詞,一般", "*", "*", "下手", "ヘタ");
+        assert!(dictionary.token_glosses(&unknown).is_empty());
+    }
+
+    #[test]
+    fn pos_codes_and_descriptions() {
+        let cases = [
+            ("v1", PosMajor::Verb, true),
+            ("Ichidan verb", PosMajor::Verb, true),
+            ("vs-i", PosMajor::Verb, true),
+            ("vs", PosMajor::Verb, false),
+            ("vs", PosMajor::Noun, true),
+            ("noun or participle which takes the aux. verb suru", PosMajor::Verb, false),
+            ("noun or participle which takes the aux. verb suru", PosMajor::Noun, true),
+            ("adverb (fukushi)", PosMajor::Adverb, true),
+            ("adverb (fukushi)", PosMajor::Verb, false),
+            ("adverbial noun (fukushitekimeishi)", PosMajor::Noun, true),
+            ("adj-na", PosMajor::AdjectivalNoun, true),
+            ("adj-na", PosMajor::Adjective, false),
+            ("adj-ix", PosMajor::Adjective, true),
+            ("adj-pn", PosMajor::Adjective, false),
+            ("pre-noun adjectival (rentaishi)", PosMajor::Adnominal, true),
+            ("auxiliary verb", PosMajor::AuxiliaryVerb, true),
+            ("auxiliary verb", PosMajor::Verb, false),
+            ("n-suf", PosMajor::Suffix, true),
+            ("num", PosMajor::Noun, true),
+            ("int", PosMajor::Interjection, true),
+            ("interjection (kandoushi)", PosMajor::Interjection, true),
+            ("expressions (phrases, clauses, etc.)", PosMajor::Noun, false),
+            ("nab", PosMajor::Noun, false),
+        ];
+        for (pos, major, fits) in cases {
+            let sense = JmdictSense {
+                pos: vec![pos.to_string()],
+                glosses: Vec::new(),
+            };
+            assert_eq!(sense_fits(&sense, &major), fits, "{pos} {major}");
+        }
+    }
+}
@@ -0,0 +1,75 @@
+/// Whether `c` is a kanji, including the iteration mark 々 and 〆, ヶ
+pub fn is_kanji(c: char) -> bool {
+    matches!(
+        c,
+        '\u{4E00}'..='\u{9FFF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{2FA1F}'
+            | '々'
+            | '〆'
+            | 'ヶ'
+    )
+}
+
+/// Whether `c` is a hiragana character
+pub fn is_hiragana(c: char) -> bool {
+    matches!(c, '\u{3041}'..='\u{309F}')
+}
+
+/// Whether `c` is a katakana character, including the long vowel mark ー
+pub fn is_katakana(c: char) -> bool {
+    matches!(c, '\u{30A0}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}') && c != 'ヶ'
+}
+
+/// Whether `c` is hiragana or katakana
+pub fn is_kana(c: char) -> bool {
+    is_hiragana(c) || is_katakana(c)
+}
+
+/// Convert katakana in `text` to hiragana, leaving other characters as-is
+///
+/// Characters without a hiragana counterpart (ヷ, ヸ, ヹ, ヺ, ー) are kept.
+pub fn katakana_to_hiragana(text: &str) -> String {
+    text.chars().map(katakana_char_to_hiragana).collect()
+}
+
+/// Convert hiragana in `text` to katakana, leaving other characters as-is
+pub fn hiragana_to_katakana(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\u{3041}'..='\u{3096}' | 'ゝ' | 'ゞ' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+/// Split kana into morae, keeping small ゃ, ゅ, ょ and small vowels with
+/// the kana before them
+///
+/// っ, ん and ー count as morae of their own: キョウト → キョ, ウ, ト.
+pub fn split_morae(kana: &str) -> Vec<String> {
+    let mut morae: Vec<String> = Vec::new();
+    for c in kana.chars() {
+        match morae.last_mut() {
+            Some(mora) if is_small_kana(c) => mora.push(c),
+            _ => morae.push(c.to_string()),
+        }
+    }
+    morae
+}
+
+fn is_small_kana(c: char) -> bool {
+    matches!(
+        c,
+        'ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ' | 'ゃ' | 'ゅ' | 'ょ' | 'ゎ'
+            | 'ァ' | 'ィ' | 'ゥ' | 'ェ' | 'ォ' | 'ャ' | 'ュ' | 'ョ' | 'ヮ'
+    )
+}
+
+fn katakana_char_to_hiragana(c: char) -> char {
+    match c {
+        '\u{30A1}'..='\u{30F6}' | 'ヽ' | 'ヾ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use roxmltree::{Document, Node, ParsingOptions};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AnalysisError;
+use crate::kana::is_kanji;
+
+/// Readings, meanings and classification of a single kanji
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KanjiInfo {
+    pub literal: char,
+    /// On'yomi (音読み) in katakana
+    pub on_readings: Vec<String>,
+    /// Kun'yomi (訓読み) in hiragana, okurigana after a `.`: た.べる
+    pub kun_readings: Vec<String>,
+    /// Readings used only in names (名乗り)
+    pub nanori: Vec<String>,
+    /// English meanings
+    pub meanings: Vec<String>,
+    pub stroke_count: u8,
+    /// School grade: 1–6 kyōiku, 8 other jōyō, 9–10 jinmeiyō
+    pub grade: Option<u8>,
+    /// Rank among the 2,500 most frequent kanji in newspapers
+    pub frequency: Option<u16>,
+    /// Level in the former four-level JLPT, 4 (easiest) to 1
+    pub jlpt: Option<u8>,
+}
+
+impl KanjiInfo {
+    /// Describe the grade, e.g. "Grade 3" or "Jōyō"
+    pub fn grade_label(&self) -> Option<String> {
+        Some(match self.grade? {
+            g @ 1..=6 => format!("Grade {g}"),
+            8 => "Jōyō".to_string(),
+            9 | 10 => "Jinmeiyō".to_string(),
+            g => format!("Grade {g}"),
+        })
+    }
+}
+
+/// Kanji lookup built from a KANJIDIC2 XML file
+///
+/// Only the fields in [`KanjiInfo`] are kept, so the dictionary is much
+/// smaller than the XML it was read from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KanjiDictionary {
+    entries: HashMap<char, KanjiInfo>,
+}
+
+impl KanjiDictionary {
+    /// Parse KANJIDIC2 XML text
+    pub fn parse(xml: &str) -> Result<Self, AnalysisError> {
+        let options = ParsingOptions {
+            allow_dtd: true,
+            ..ParsingOptions::default()
+        };
+        let document = Document::parse_with_options(xml, options)
+            .map_err(|e| AnalysisError::KanjiDictionaryParse(e.to_string()))?;
+
+        let entries = document
+            .root_element()
+            .children()
+            .filter(|n| n.has_tag_name("character"))
+            .filter_map(parse_character)
+            .map(|info| (info.literal, info))
+            .collect();
+        Ok(Self { entries })
+    }
+
+    /// Read and parse a KANJIDIC2 XML file
+    pub fn load(path: &Path) -> Result<Self, AnalysisError> {
+        let xml = std::fs::read_to_string(path)
+            .map_err(|e| AnalysisError::KanjiDictionaryRead(format!("{}: {e}", path.display())))?;
+        Self::parse(&xml)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, literal: char) -> Option<&KanjiInfo> {
+        self.entries.get(&literal)
+    }
+
+    /// Information on each distinct kanji of `text`, in order of appearance
+    pub fn lookup_text(&self, text: &str) -> Vec<&KanjiInfo> {
+        let mut found: Vec<&KanjiInfo> = Vec::new();
+        for c in text.chars().filter(|&c| is_kanji(c)) {
+            if let Some(info) = self.get(c)
+                && !found.iter().any(|f| f.literal == c)
+            {
+                found.push(info);
+            }
+        }
+        found
+    }
+}
+
+fn parse_character(node: Node) -> Option<KanjiInfo> {
+    let literal = child_text(node, "literal")?.chars().next()?;
+    let misc = node.children().find(|n| n.has_tag_name("misc"));
+    let misc_number = |name: &str| misc.and_then(|m| child_text(m, name)?.parse().ok());
+
+    let mut info = KanjiInfo {
+        literal,
+        on_readings: Vec::new(),
+        kun_readings: Vec::new(),
+        nanori: Vec::new(),
+        meanings: Vec::new(),
+        // The first stroke count is the accepted one, others are common miscounts
+        stroke_count: misc_number("stroke_count").unwrap_or(0),
+        grade: misc_number("grade"),
+        frequency: misc.and_then(|m| child_text(m, "freq")?.parse().ok()),
+        jlpt: misc_number("jlpt"),
+    };
+
+    let Some(reading_meaning) = node.children().find(|n| n.has_tag_name("reading_meaning")) else {
+        return Some(info);
+    };
+    for child in reading_meaning.descendants().filter(|n| n.is_element()) {
+        let Some(text) = child.text().map(str::to_string) else {
+            continue;
+        };
+        match child.tag_name().name() {
+            "reading" => match child.attribute("r_type") {
+                Some("ja_on") => info.on_readings.push(text),
+                Some("ja_kun") => info.kun_readings.push(text),
+                _ => {}
+            },
+            "meaning" if child.attribute("m_lang").is_none() => info.meanings.push(text),
+            "nanori" => info.nanori.push(text),
+            _ => {}
+        }
+    }
+    Some(info)
+}
+
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.children().find(|n| n.has_tag_name(name))?.text()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KANJIDIC: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE kanjidic2 [
+<!ELEMENT kanjidic2 (header,character*)>
+]>
+<kanjidic2>
+<header><file_version>4</file_version></header>
+<character>
+<literal>食</literal>
+<misc>
+<grade>2</grade>
+<stroke_count>9</stroke_count>
+<stroke_count>8</stroke_count>
+<freq>328</freq>
+<jlpt>4</jlpt>
+</misc>
+<reading_meaning>
+<rmgroup>
+<reading r_type="pinyin">shi2</reading>
+<reading r_type="ja_on">ショク</reading>
+<reading r_type="ja_on">ジキ</reading>
+<reading r_type="ja_kun">く.う</reading>
+<reading r_type="ja_kun">た.べる</reading>
+<meaning>eat</meaning>
+<meaning>food</meaning>
+<meaning m_lang="fr">manger</meaning>
+<meaning m_lang="es">comer</meaning>
+</rmgroup>
+<nanori>け</nanori>
+</reading_meaning>
+</character>
+<character>
+<literal>亘</literal>
+<misc>
+<grade>9</grade>
+<stroke_count>6</stroke_count>
+</misc>
+</character>
+</kanjidic2>
+"#;
+
+    #[test]
+    fn parse_character() {
+        let dictionary = KanjiDictionary::parse(KANJIDIC).unwrap();
+        assert_eq!(dictionary.len(), 2);
+
+        let taberu = dictionary.get('食').unwrap();
+        assert_eq!(taberu.on_readings, ["ショク", "ジキ"]);
+        assert_eq!(taberu.kun_readings, ["く.う", "た.べる"]);
+        assert_eq!(taberu.nanori, ["け"]);
+        // Meanings in other languages are left out
+        assert_eq!(taberu.meanings, ["eat", "food"]);
+        // The first stroke count is the accepted one
+        assert_eq!(taberu.stroke_count, 9);
+        assert_eq!(taberu.grade, Some(2));
+        assert_eq!(taberu.grade_label().as_deref(), Some("Grade 2"));
+        assert_eq!(taberu.frequency, Some(328));
+        assert_eq!(taberu.jlpt, Some(4));
+    }
+
+    #[test]
+    fn optional_fields_may_be_missing() {
+        let dictionary = KanjiDictionary::parse(KANJIDIC).unwrap();
+        let wataru = dictionary.get('亘').unwrap();
+        assert_eq!(wataru.stroke_count, 6);
+        assert_eq!(wataru.grade_label().as_deref(), Some("Jinmeiyō"));
+        assert_eq!(wataru.frequency, None);
+        assert_eq!(wataru.jlpt, None);
+        assert!(wataru.on_readings.is_empty() && wataru.meanings.is_empty());
+    }
+
+    #[test]
+    fn lookup_text_keeps_first_appearance() {
+        let dictionary = KanjiDictionary::parse(KANJIDIC).unwrap();
+        let found: Vec<char> = dictionary
+            .lookup_text("食べ物を食べた、亘と")
+            .iter()
+            .map(|info| info.literal)
+            .collect();
+        // 物 is not in the dictionary
+        assert_eq!(found, ['食', '亘']);
+    }
+
+    #[test]
+    fn invalid_xml() {
+        assert!(matches!(
+            KanjiDictionary::parse("<kanjidic2><character>"),
+            Err(AnalysisError::KanjiDictionaryParse(_))
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AnalysisError;
+use crate::pos::{ConjugationForm, ConjugationType, PosMajor, PosSub};
+
+const BUILTIN_LABELS: &str = include_str!("../data/labels.json");
+
+/// Language grammar labels are shown in
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum LabelLanguage {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "ko")]
+    Korean,
+}
+
+impl LabelLanguage {
+    pub const ALL: [LabelLanguage; 2] = [LabelLanguage::English, LabelLanguage::Korean];
+
+    /// Language code used in the catalog and in form values, e.g. `ko`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LabelLanguage::English => "en",
+            LabelLanguage::Korean => "ko",
+        }
+    }
+
+    /// Name of the language in the language itself
+    pub fn label(&self) -> &'static str {
+        match self {
+            LabelLanguage::English => "English",
+            LabelLanguage::Korean => "한국어",
+        }
+    }
+}
+
+impl FromStr for LabelLanguage {
+    type Err = AnalysisError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "en" | "english" => Ok(LabelLanguage::English),
+            "ko" | "korean" => Ok(LabelLanguage::Korean),
+            _ => Err(AnalysisError::UnknownLabelLanguage(s.to_string())),
+        }
+    }
+}
+
+/// A grammar label in one language
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LabelText {
+    /// Short name, e.g. "Case particle"
+    pub name: String,
+    /// What the label means, written for learners
+    pub explanation: String,
+}
+
+/// A grammar label in every supported language
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LabelTranslations {
+    pub en: LabelText,
+    pub ko: LabelText,
+}
+
+impl LabelTranslations {
+    pub fn get(&self, language: LabelLanguage) -> &LabelText {
+        match language {
+            LabelLanguage::English => &self.en,
+            LabelLanguage::Korean => &self.ko,
+        }
+    }
+}
+
+/// Translations of dictionary labels, keyed by the Japanese label
+///
+/// The catalog is written as JSON, see `data/labels.json` for the built-in
+/// one, which covers the POS categories, subcategories, conjugation types
+/// and conjugation forms of IPADIC and the common ones of UniDic.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct LabelCatalog {
+    pub pos_major: BTreeMap<String, LabelTranslations>,
+    pub pos_sub: BTreeMap<String, LabelTranslations>,
+    pub conjugation_type: BTreeMap<String, LabelTranslations>,
+    pub conjugation_form: BTreeMap<String, LabelTranslations>,
+}
+
+impl LabelCatalog {
+    /// Parse a catalog from JSON text
+    pub fn parse(json: &str) -> Result<Self, AnalysisError> {
+        serde_json::from_str(json).map_err(|e| AnalysisError::LabelCatalogParse(e.to_string()))
+    }
+
+    /// The catalog shipped with the crate
+    pub fn builtin() -> &'static LabelCatalog {
+        static BUILTIN: OnceLock<LabelCatalog> = OnceLock::new();
+        BUILTIN.get_or_init(|| Self::parse(BUILTIN_LABELS).expect("built-in labels are valid"))
+    }
+
+    pub fn pos_major(&self, major: &PosMajor, language: LabelLanguage) -> Option<&LabelText> {
+        Some(self.pos_major.get(major.as_str())?.get(language))
+    }
+
+    pub fn pos_sub(&self, sub: &PosSub, language: LabelLanguage) -> Option<&LabelText> {
+        Some(self.pos_sub.get(sub.as_str())?.get(language))
+    }
+
+    pub fn conjugation_type(
+        &self,
+        conjugation_type: &ConjugationType,
+        language: LabelLanguage,
+    ) -> Option<&LabelText> {
+        Some(
+            self.conjugation_type
+                .get(conjugation_type.as_str())?
+                .get(language),
+        )
+    }
+
+    pub fn conjugation_form(
+        &self,
+        conjugation_form: &ConjugationForm,
+        language: LabelLanguage,
+    ) -> Option<&LabelText> {
+        Some(
+            self.conjugation_form
+                .get(conjugation_form.as_str())?
+                .get(language),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_catalog_covers_every_label() {
+        let catalog = LabelCatalog::builtin();
+        for major in PosMajor::ALL {
+            assert!(catalog.pos_major.contains_key(major.as_str()), "{major}");
+        }
+        for sub in PosSub::ALL {
+            assert!(catalog.pos_sub.contains_key(sub.as_str()), "{sub}");
+        }
+        for conjugation_type in ConjugationType::ALL {
+            let label = conjugation_type.as_str();
+            assert!(catalog.conjugation_type.contains_key(label), "{label}");
+        }
+        for form in ConjugationForm::ALL {
+            assert!(catalog.conjugation_form.contains_key(form.as_str()), "{form}");
+        }
+    }
+
+    #[test]
+    fn language_codes_match_serde() {
+        for language in LabelLanguage::ALL {
+            let json = serde_json::to_string(&language).unwrap();
+            assert_eq!(json, format!("\"{}\"", language.as_str()));
+            assert_eq!(language.as_str().parse::<LabelLanguage>().unwrap(), language);
+        }
+    }
+}
@@ -1,7 +1,59 @@
+pub mod accent;
+#[cfg(feature = "tokenizer")]
+pub mod analyzer;
+pub mod batch;
+pub mod bunsetsu;
+pub mod config;
+pub mod conjugation;
+pub mod dependency;
 pub mod dialogue;
+pub mod difficulty;
 pub mod error;
+pub mod furigana;
+pub mod grammar;
+pub mod inflection;
+pub mod jlpt;
+pub mod jmdict;
+pub mod kana;
+pub mod kanji;
+pub mod labels;
+pub mod normalize;
+pub mod numeral;
+pub mod pos;
+pub mod register;
+pub mod sentence;
+pub mod speech_style;
+#[cfg(test)]
+mod test_support;
 pub mod tokenizer;
+pub mod transliteration;
+pub mod user_dictionary;
 
+pub use accent::*;
+#[cfg(feature = "tokenizer")]
+pub use analyzer::*;
+pub use batch::*;
+pub use bunsetsu::*;
+pub use config::*;
+pub use conjugation::*;
+pub use dependency::*;
 pub use dialogue::*;
+pub use difficulty::*;
 pub use error::*;
+pub use furigana::*;
+pub use grammar::*;
+pub use inflection::*;
+pub use jlpt::*;
+pub use jmdict::*;
+pub use kana::*;
+pub use kanji::*;
+pub use labels::*;
+pub use normalize::*;
+pub use numeral::*;
+pub use pos::*;
+pub use register::*;
+pub use sentence::*;
+pub use speech_style::*;
 pub use tokenizer::*;
+pub use transliteration::*;
+pub use user_dictionary::*;
@@ -0,0 +1,189 @@
+use std::ops::Range;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::canonical_combining_class;
+
+use crate::error::AnalysisError;
+
+/// Unicode normalization applied to text before it is tokenized
+///
+/// Token surfaces are read from the normalized text, so with NFKC they can
+/// differ from the text shown to the user: ？ → ?, ３ → 3, … → ... . Use the
+/// token offsets, which point into the original text, to display tokens.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Normalization {
+    /// Tokenize the text as given
+    None,
+    /// Compose combining marks: か + ゙ → が
+    Nfc,
+    /// Also fold compatibility characters: ｶﾞ → ガ, ＡＢＣ１ → ABC1, ㍻ → 平成,
+    /// including full-width punctuation: ？！ → ?!
+    #[default]
+    Nfkc,
+}
+
+impl Normalization {
+    /// Name used in configuration, e.g. `nfkc`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Normalization::None => "none",
+            Normalization::Nfc => "nfc",
+            Normalization::Nfkc => "nfkc",
+        }
+    }
+}
+
+impl FromStr for Normalization {
+    type Err = AnalysisError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "none" => Ok(Normalization::None),
+            "nfc" => Ok(Normalization::Nfc),
+            "nfkc" => Ok(Normalization::Nfkc),
+            _ => Err(AnalysisError::UnknownNormalization(s.to_string())),
+        }
+    }
+}
+
+/// Normalized text with a map back to the text it was normalized from
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct NormalizedText {
+    pub text: String,
+    /// For each char of `text`, the char offset in the original text it came
+    /// from, followed by the original's char length
+    pub offsets: Vec<usize>,
+}
+
+impl NormalizedText {
+    /// Normalize `original` with `normalization`
+    ///
+    /// The text is normalized one base character at a time, together with
+    /// the combining marks that follow it, so every normalized char can be
+    /// traced back to the character it came from.
+    pub fn new(original: &str, normalization: Normalization) -> Self {
+        let mut normalized = Self::default();
+        let mut cluster = String::new();
+        let mut cluster_start = 0;
+        let mut length = 0;
+
+        for (index, c) in original.chars().enumerate() {
+            if !cluster.is_empty() && !is_combining(c) {
+                normalized.push_cluster(&cluster, cluster_start, normalization);
+                cluster.clear();
+                cluster_start = index;
+            }
+            cluster.push(c);
+            length = index + 1;
+        }
+        normalized.push_cluster(&cluster, cluster_start, normalization);
+        normalized.offsets.push(length);
+        normalized
+    }
+
+    fn push_cluster(&mut self, cluster: &str, start: usize, normalization: Normalization) {
+        let before = self.text.len();
+        match normalization {
+            Normalization::None => self.text.push_str(cluster),
+            Normalization::Nfc => self.text.extend(cluster.nfc()),
+            Normalization::Nfkc => self.text.extend(cluster.nfkc()),
+        }
+        let added = self.text[before..].chars().count();
+        self.offsets.extend(std::iter::repeat_n(start, added));
+    }
+
+    /// Whether normalization left the text as it was
+    pub fn is_unchanged(&self, original: &str) -> bool {
+        self.text == original
+    }
+
+    /// Char range in the original text of the normalized char range `chars`
+    ///
+    /// A range that starts or ends inside the expansion of one original
+    /// character, like 平 of ㍻ → 平成, is widened to that whole character.
+    pub fn original_span(&self, chars: Range<usize>) -> Range<usize> {
+        let last = self.offsets.len() - 1;
+        let start = self.offsets[chars.start.min(last)];
+        if chars.end <= chars.start {
+            return start..start;
+        }
+        let final_source = self.offsets[(chars.end - 1).min(last)];
+        let end = self.offsets[chars.end.min(last)..]
+            .iter()
+            .copied()
+            .find(|&offset| offset > final_source)
+            .unwrap_or(self.offsets[last]);
+        start..end
+    }
+}
+
+/// Marks that attach to the preceding character, including the half-width
+/// sound marks ﾞ and ﾟ that NFKC turns into combining ones
+fn is_combining(c: char) -> bool {
+    canonical_combining_class(c) != 0 || matches!(c, '\u{FF9E}' | '\u{FF9F}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_text_maps_to_itself() {
+        let normalized = NormalizedText::new("漢字です", Normalization::Nfkc);
+        assert!(normalized.is_unchanged("漢字です"));
+        assert_eq!(normalized.original_span(0..2), 0..2);
+        assert_eq!(normalized.original_span(2..4), 2..4);
+    }
+
+    #[test]
+    fn folded_characters_map_back() {
+        let normalized = NormalizedText::new("ＡＢ１？", Normalization::Nfkc);
+        assert_eq!(normalized.text, "AB1?");
+        assert_eq!(normalized.original_span(1..3), 1..3);
+        assert_eq!(normalized.original_span(3..4), 3..4);
+
+        // ｶﾞ is two chars that fold into one
+        let normalized = NormalizedText::new("ｶﾞｷﾞ", Normalization::Nfkc);
+        assert_eq!(normalized.text, "ガギ");
+        assert_eq!(normalized.original_span(0..1), 0..2);
+        assert_eq!(normalized.original_span(1..2), 2..4);
+
+        let normalized = NormalizedText::new("か\u{3099}", Normalization::Nfc);
+        assert_eq!(normalized.text, "が");
+        assert_eq!(normalized.original_span(0..1), 0..2);
+    }
+
+    #[test]
+    fn expansions_widen_to_the_whole_character() {
+        let normalized = NormalizedText::new("㍻元年", Normalization::Nfkc);
+        assert_eq!(normalized.text, "平成元年");
+        assert_eq!(normalized.original_span(0..1), 0..1);
+        assert_eq!(normalized.original_span(1..2), 0..1);
+        assert_eq!(normalized.original_span(0..2), 0..1);
+        assert_eq!(normalized.original_span(1..3), 0..2);
+        assert_eq!(normalized.original_span(2..4), 1..3);
+
+        let normalized = NormalizedText::new("え…", Normalization::Nfkc);
+        assert_eq!(normalized.text, "え...");
+        assert_eq!(normalized.original_span(1..4), 1..2);
+        assert_eq!(normalized.original_span(2..3), 1..2);
+    }
+
+    #[test]
+    fn empty_ranges() {
+        let normalized = NormalizedText::new("㍻元年", Normalization::Nfkc);
+        assert_eq!(normalized.original_span(2..2), 1..1);
+        assert_eq!(normalized.original_span(4..4), 3..3);
+        assert_eq!(NormalizedText::new("", Normalization::Nfkc).original_span(0..0), 0..0);
+    }
+
+    #[test]
+    fn none_keeps_the_text() {
+        let normalized = NormalizedText::new("ｶﾞ", Normalization::None);
+        assert_eq!(normalized.text, "ｶﾞ");
+        assert_eq!(normalized.original_span(0..2), 0..2);
+    }
+}
@@ -0,0 +1,654 @@
+use serde::{Deserialize, Serialize};
+
+use crate::kana::katakana_to_hiragana;
+use crate::pos::{PosMajor, PosSub};
+use crate::tokenizer::{AnalyzedSentence, AnalyzedToken};
+
+/// Readings of the digits 0 to 9
+const DIGITS: [&str; 10] = [
+    "ぜろ",
+    "いち",
+    "に",
+    "さん",
+    "よん",
+    "ご",
+    "ろく",
+    "なな",
+    "はち",
+    "きゅう",
+];
+
+/// Reading endings that turn into a small っ before some counters:
+/// 一本 いっぽん, 六個 ろっこ, 十冊 じゅっさつ
+const GEMINATING: [(&str, &str); 5] = [
+    ("いち", "いっ"),
+    ("はち", "はっ"),
+    ("じゅう", "じゅっ"),
+    ("ろく", "ろっ"),
+    ("ひゃく", "ひゃっ"),
+];
+
+/// Sound changes between a number and the counter after it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Euphony {
+    /// No change: 三枚 さんまい
+    None,
+    /// か-row counters: 1, 6, 8, 10 and 100 take っ
+    K,
+    /// さ-row counters: 1, 8 and 10 take っ
+    S,
+    /// た-row counters: 1, 8 and 10 take っ
+    T,
+    /// は-row counters: 1, 6, 8, 10 and 100 take っ and the counter turns
+    /// into ぱ-row: 一本 いっぽん
+    H,
+}
+
+/// What happens to a counter after a number ending in ん
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AfterN {
+    Keep,
+    /// Voiced after さん, せん and まん, but not よん: 三本 さんぼん, 四本 よんほん
+    Voiced,
+    /// ぱ-row after every ん: 三分 さんぷん, 四分 よんぷん
+    SemiVoiced,
+}
+
+struct Counter {
+    surfaces: &'static [&'static str],
+    reading: &'static str,
+    euphony: Euphony,
+    after_n: AfterN,
+    /// Readings of whole counts that follow no rule: 二人 ふたり
+    irregular: &'static [(u64, &'static str)],
+    /// Readings of a final digit that follow no rule, also inside larger
+    /// counts: 四時 よじ, 十四時 じゅうよじ
+    irregular_final: &'static [(u64, &'static str)],
+}
+
+impl Counter {
+    const fn new(
+        surfaces: &'static [&'static str],
+        reading: &'static str,
+        euphony: Euphony,
+        after_n: AfterN,
+    ) -> Self {
+        Counter {
+            surfaces,
+            reading,
+            euphony,
+            after_n,
+            irregular: &[],
+            irregular_final: &[],
+        }
+    }
+
+    const fn plain(surfaces: &'static [&'static str], reading: &'static str) -> Self {
+        Counter::new(surfaces, reading, Euphony::None, AfterN::Keep)
+    }
+}
+
+/// Counters whose readings change with the number before them
+const COUNTERS: &[Counter] = &[
+    Counter::new(&["本"], "ほん", Euphony::H, AfterN::Voiced),
+    Counter::new(&["杯"], "はい", Euphony::H, AfterN::Voiced),
+    Counter::new(&["匹"], "ひき", Euphony::H, AfterN::Voiced),
+    Counter::new(&["分"], "ふん", Euphony::H, AfterN::SemiVoiced),
+    Counter::new(&["泊"], "はく", Euphony::H, AfterN::SemiVoiced),
+    Counter::new(&["発"], "はつ", Euphony::H, AfterN::SemiVoiced),
+    Counter::new(&["歩"], "ほ", Euphony::H, AfterN::SemiVoiced),
+    Counter::new(&["個", "コ"], "こ", Euphony::K, AfterN::Keep),
+    Counter::new(&["回"], "かい", Euphony::K, AfterN::Keep),
+    Counter::new(&["階"], "かい", Euphony::K, AfterN::Voiced),
+    Counter::new(&["軒"], "けん", Euphony::K, AfterN::Voiced),
+    Counter::new(&["件"], "けん", Euphony::K, AfterN::Keep),
+    Counter::new(&["曲"], "きょく", Euphony::K, AfterN::Keep),
+    Counter::new(
+        &["ヶ月", "ケ月", "か月", "カ月", "ヵ月", "箇月"],
+        "かげつ",
+        Euphony::K,
+        AfterN::Keep,
+    ),
+    Counter::new(&["冊"], "さつ", Euphony::S, AfterN::Keep),
+    Counter::new(&["週間"], "しゅうかん", Euphony::S, AfterN::Keep),
+    Counter::new(&["足"], "そく", Euphony::S, AfterN::Voiced),
+    Counter {
+        irregular: &[(20, "はたち")],
+        ..Counter::new(&["歳", "才"], "さい", Euphony::S, AfterN::Keep)
+    },
+    Counter::new(&["頭"], "とう", Euphony::T, AfterN::Keep),
+    Counter::new(&["通"], "つう", Euphony::T, AfterN::Keep),
+    Counter::new(&["着"], "ちゃく", Euphony::T, AfterN::Keep),
+    Counter::new(&["点"], "てん", Euphony::T, AfterN::Keep),
+    Counter {
+        irregular: &[(1, "ひとり"), (2, "ふたり")],
+        irregular_final: &[(4, "よにん")],
+        ..Counter::plain(&["人"], "にん")
+    },
+    Counter {
+        irregular: &[
+            (1, "ひとつ"),
+            (2, "ふたつ"),
+            (3, "みっつ"),
+            (4, "よっつ"),
+            (5, "いつつ"),
+            (6, "むっつ"),
+            (7, "ななつ"),
+            (8, "やっつ"),
+            (9, "ここのつ"),
+            (10, "とお"),
+        ],
+        ..Counter::plain(&["つ"], "つ")
+    },
+    Counter {
+        // 一日 is left to the regular いちにち; as a date it is ついたち
+        irregular: &[
+            (2, "ふつか"),
+            (3, "みっか"),
+            (5, "いつか"),
+            (6, "むいか"),
+            (7, "なのか"),
+            (8, "ようか"),
+            (9, "ここのか"),
+            (10, "とおか"),
+            (20, "はつか"),
+        ],
+        irregular_final: &[(4, "よっか")],
+        ..Counter::plain(&["日"], "にち")
+    },
+    Counter {
+        irregular_final: &[(4, "しがつ"), (7, "しちがつ"), (9, "くがつ")],
+        ..Counter::plain(&["月"], "がつ")
+    },
+    Counter {
+        irregular_final: &[(4, "よじ"), (7, "しちじ"), (9, "くじ")],
+        ..Counter::plain(&["時"], "じ")
+    },
+    Counter {
+        irregular_final: &[(4, "よじかん"), (9, "くじかん")],
+        ..Counter::plain(&["時間"], "じかん")
+    },
+    Counter {
+        irregular_final: &[(4, "よねん")],
+        ..Counter::plain(&["年"], "ねん")
+    },
+    Counter {
+        irregular_final: &[(4, "よえん")],
+        ..Counter::plain(&["円"], "えん")
+    },
+    Counter::plain(&["枚"], "まい"),
+    Counter::plain(&["台"], "だい"),
+    Counter::plain(&["番"], "ばん"),
+    Counter::plain(&["度"], "ど"),
+    Counter::plain(&["秒"], "びょう"),
+    Counter::plain(&["倍"], "ばい"),
+];
+
+/// A number in a sentence, with the counter that follows it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NumeralExpression {
+    /// Index of the first token
+    pub start: usize,
+    /// Index one past the last token, including the counter
+    pub end: usize,
+    /// Surface of the number and counter, e.g. 三百五十円
+    pub surface: String,
+    pub value: u64,
+    /// Counter (助数詞) after the number, e.g. 円
+    pub counter: Option<String>,
+    /// Hiragana reading with sound changes applied, e.g. さんびゃくごじゅうえん
+    pub reading: String,
+}
+
+/// Find the numbers in `sentence` and the counters that follow them
+///
+/// Kanji numerals (三百五十), Arabic numerals in half or full width (3, ３,
+/// 1,000) and mixes of both (3万) are recognized. Numbers the tokenizer
+/// splits into several tokens are joined back together.
+pub fn find_numerals(sentence: &AnalyzedSentence) -> Vec<NumeralExpression> {
+    let tokens = &sentence.tokens;
+    let mut numerals = Vec::new();
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let mut end = index;
+        while end < tokens.len()
+            && (is_number_token(&tokens[end])
+                || (end > index
+                    && is_separator(&tokens[end].surface)
+                    && tokens.get(end + 1).is_some_and(is_number_token)))
+        {
+            end += 1;
+        }
+        if end == index {
+            numerals.extend(fused_numeral(tokens, index));
+            index += 1;
+            continue;
+        }
+
+        let number: String = tokens[index..end]
+            .iter()
+            .map(|t| t.surface.as_str())
+            .collect();
+        let Some(value) = parse_numeral(&number) else {
+            index = end;
+            continue;
+        };
+        let counter = tokens.get(end).filter(|t| is_counter(t));
+        let reading = match counter {
+            Some(counter) => {
+                let spoken = if counter.reading == "*" {
+                    counter.surface.clone()
+                } else {
+                    katakana_to_hiragana(&counter.reading)
+                };
+                read_count(value, &counter.surface, &spoken)
+            }
+            None => read_number(value),
+        };
+        let stop = end + counter.is_some() as usize;
+        numerals.push(NumeralExpression {
+            start: index,
+            end: stop,
+            surface: tokens[index..stop]
+                .iter()
+                .map(|t| t.surface.as_str())
+                .collect(),
+            value,
+            counter: counter.map(|t| t.surface.clone()),
+            reading,
+        });
+        index = stop;
+    }
+    numerals
+}
+
+/// A number and counter the tokenizer kept as one word, like 一人
+fn fused_numeral(tokens: &[AnalyzedToken], index: usize) -> Option<NumeralExpression> {
+    let token = &tokens[index];
+    let word_like = matches!(
+        token.pos.sub1,
+        PosSub::General | PosSub::CommonNoun | PosSub::Number | PosSub::Numeral
+    );
+    if token.pos.major != PosMajor::Noun || !word_like {
+        return None;
+    }
+    let split = token
+        .surface
+        .char_indices()
+        .find(|&(_, c)| !is_numeral_char(c))
+        .map(|(i, _)| i)?;
+    let (number, counter) = token.surface.split_at(split);
+    let known = COUNTERS.iter().find(|c| c.surfaces.contains(&counter))?;
+    let value = parse_numeral(number)?;
+    Some(NumeralExpression {
+        start: index,
+        end: index + 1,
+        surface: token.surface.clone(),
+        value,
+        counter: Some(counter.to_string()),
+        reading: read_count(value, counter, known.reading),
+    })
+}
+
+fn is_number_token(token: &AnalyzedToken) -> bool {
+    matches!(token.pos.sub1, PosSub::Number | PosSub::Numeral)
+        && token.surface.chars().all(is_numeral_char)
+}
+
+fn is_numeral_char(c: char) -> bool {
+    digit_value(c).is_some() || unit_value(c).is_some() || is_separator_char(c)
+}
+
+fn is_separator(surface: &str) -> bool {
+    let mut chars = surface.chars();
+    chars.next().is_some_and(is_separator_char) && chars.next().is_none()
+}
+
+fn is_separator_char(c: char) -> bool {
+    matches!(c, ',' | '，')
+}
+
+fn is_counter(token: &AnalyzedToken) -> bool {
+    [&token.pos.sub1, &token.pos.sub2, &token.pos.sub3].contains(&&PosSub::Counter)
+        || COUNTERS
+            .iter()
+            .any(|c| c.surfaces.contains(&token.surface.as_str()))
+}
+
+fn digit_value(c: char) -> Option<u64> {
+    match c {
+        '0'..='9' => c.to_digit(10).map(u64::from),
+        '０'..='９' => Some(c as u64 - '０' as u64),
+        '〇' | '零' => Some(0),
+        '一' | '壱' => Some(1),
+        '二' | '弐' => Some(2),
+        '三' | '参' => Some(3),
+        '四' => Some(4),
+        '五' => Some(5),
+        '六' => Some(6),
+        '七' => Some(7),
+        '八' => Some(8),
+        '九' => Some(9),
+        _ => None,
+    }
+}
+
+fn unit_value(c: char) -> Option<u64> {
+    match c {
+        '十' | '拾' => Some(10),
+        '百' => Some(100),
+        '千' => Some(1_000),
+        '万' => Some(10_000),
+        '億' => Some(100_000_000),
+        '兆' => Some(1_000_000_000_000),
+        _ => None,
+    }
+}
+
+/// Value of a kanji or Arabic numeral: 三百五十 → 350, 3万 → 30000,
+/// 1,000 → 1000, 二〇二四 → 2024
+pub fn parse_numeral(text: &str) -> Option<u64> {
+    if text.chars().all(is_separator_char) {
+        return None;
+    }
+    let mut total: u64 = 0;
+    // Value below the current 万 group
+    let mut group: u64 = 0;
+    // Digits written positionally since the last unit
+    let mut digits: Option<u64> = None;
+
+    for c in text.chars() {
+        if is_separator_char(c) {
+            continue;
+        }
+        if let Some(digit) = digit_value(c) {
+            digits = Some(digits.unwrap_or(0).checked_mul(10)?.checked_add(digit)?);
+            continue;
+        }
+        let unit = unit_value(c)?;
+        if unit < 10_000 {
+            group = group.checked_add(digits.unwrap_or(1).checked_mul(unit)?)?;
+        } else {
+            let multiplier = match group.checked_add(digits.unwrap_or(0))? {
+                0 => 1,
+                n => n,
+            };
+            total = total.checked_add(multiplier.checked_mul(unit)?)?;
+            group = 0;
+        }
+        digits = None;
+    }
+
+    total.checked_add(group)?.checked_add(digits.unwrap_or(0))
+}
+
+/// Hiragana reading of a number: 350 → さんびゃくごじゅう, 8000 → はっせん
+pub fn read_number(value: u64) -> String {
+    if value == 0 {
+        return DIGITS[0].to_string();
+    }
+    let groups = [
+        (1_000_000_000_000, "ちょう", Euphony::T),
+        (100_000_000, "おく", Euphony::None),
+        (10_000, "まん", Euphony::None),
+    ];
+    let mut reading = String::new();
+    let mut rest = value;
+    for (unit, name, euphony) in groups {
+        let count = rest / unit;
+        rest %= unit;
+        if count > 0 {
+            reading += &join(&read_below_10000(count), name, euphony, AfterN::Keep);
+        }
+    }
+    reading + &read_below_10000(rest)
+}
+
+/// Reading of 0 to 9999, empty for 0
+fn read_below_10000(value: u64) -> String {
+    let places = [
+        (1_000, "せん", Euphony::S, AfterN::Voiced),
+        (100, "ひゃく", Euphony::H, AfterN::Voiced),
+        (10, "じゅう", Euphony::None, AfterN::Keep),
+    ];
+    let mut reading = String::new();
+    let mut rest = value % 10_000;
+    for (place, name, euphony, after_n) in places {
+        let digit = rest / place;
+        rest %= place;
+        match digit {
+            0 => {}
+            1 => reading += name,
+            _ => reading += &join(DIGITS[digit as usize], name, euphony, after_n),
+        }
+    }
+    if rest > 0 {
+        reading += DIGITS[rest as usize];
+    }
+    reading
+}
+
+/// Hiragana reading of a count: (3, 本) → さんぼん, (1, 分) → いっぷん
+///
+/// `counter_reading` is used for counters without known sound changes.
+pub fn read_count(value: u64, counter: &str, counter_reading: &str) -> String {
+    let Some(rule) = COUNTERS.iter().find(|c| c.surfaces.contains(&counter)) else {
+        return read_number(value) + counter_reading;
+    };
+    if let Some(&(_, reading)) = rule.irregular.iter().find(|(n, _)| *n == value) {
+        return reading.to_string();
+    }
+    let last = value % 10;
+    if let Some(&(_, reading)) = rule.irregular_final.iter().find(|(n, _)| *n == last) {
+        let rest = value - last;
+        let prefix = if rest == 0 {
+            String::new()
+        } else {
+            read_number(rest)
+        };
+        return prefix + reading;
+    }
+    join(
+        &read_number(value),
+        rule.reading,
+        rule.euphony,
+        rule.after_n,
+    )
+}
+
+/// Join a number reading and the counter reading after it, applying the
+/// counter's sound changes
+fn join(number: &str, counter: &str, euphony: Euphony, after_n: AfterN) -> String {
+    let geminating: &[(&str, &str)] = match euphony {
+        Euphony::None => &[],
+        Euphony::S | Euphony::T => &GEMINATING[..3],
+        Euphony::K | Euphony::H => &GEMINATING,
+    };
+    for (ending, small) in geminating {
+        if let Some(stem) = number.strip_suffix(ending) {
+            let counter = if euphony == Euphony::H {
+                semi_voiced(counter)
+            } else {
+                counter.to_string()
+            };
+            return format!("{stem}{small}{counter}");
+        }
+    }
+
+    let counter = match after_n {
+        AfterN::Voiced if number.ends_with('ん') && !number.ends_with("よん") => voiced(counter),
+        AfterN::SemiVoiced if number.ends_with('ん') => semi_voiced(counter),
+        _ => counter.to_string(),
+    };
+    format!("{number}{counter}")
+}
+
+/// Add dakuten to the first kana: ほん → ぼん
+fn voiced(reading: &str) -> String {
+    replace_first(reading, "かきくけこさしすせそたちつてとはひふへほ", 1)
+}
+
+/// Add handakuten to the first kana: ほん → ぽん
+fn semi_voiced(reading: &str) -> String {
+    replace_first(reading, "はひふへほ", 2)
+}
+
+/// Shift the first char by `offset` code points if it is one of `row`;
+/// voiced and semi-voiced kana directly follow their plain form
+fn replace_first(reading: &str, row: &str, offset: u32) -> String {
+    let mut chars = reading.chars();
+    match chars.next() {
+        Some(first) if row.contains(first) => char::from_u32(first as u32 + offset)
+            .map(|c| std::iter::once(c).chain(chars).collect())
+            .unwrap_or_else(|| reading.to_string()),
+        _ => reading.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sentence, token};
+
+    #[test]
+    fn parses_numerals() {
+        for (text, value) in [
+            ("三", 3),
+            ("十", 10),
+            ("三百五十", 350),
+            ("六百", 600),
+            ("八千", 8_000),
+            ("二十", 20),
+            ("千二百三十四万五千六百七十八", 12_345_678),
+            ("一億", 100_000_000),
+            ("二〇二四", 2_024),
+            ("1,000", 1_000),
+            ("３", 3),
+            ("3万", 30_000),
+            ("2万5千", 25_000),
+        ] {
+            assert_eq!(parse_numeral(text), Some(value), "{text}");
+        }
+        for text in ["", ",", "三個", "a"] {
+            assert_eq!(parse_numeral(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn reads_numbers() {
+        for (value, reading) in [
+            (0, "ぜろ"),
+            (4, "よん"),
+            (14, "じゅうよん"),
+            (100, "ひゃく"),
+            (300, "さんびゃく"),
+            (600, "ろっぴゃく"),
+            (800, "はっぴゃく"),
+            (1_000, "せん"),
+            (3_000, "さんぜん"),
+            (8_000, "はっせん"),
+            (350, "さんびゃくごじゅう"),
+            (10_000, "いちまん"),
+            (1_000_000_000_000, "いっちょう"),
+        ] {
+            assert_eq!(read_number(value), reading, "{value}");
+        }
+    }
+
+    #[test]
+    fn reads_counts() {
+        for (value, counter, reading) in [
+            (1, "本", "いっぽん"),
+            (2, "本", "にほん"),
+            (3, "本", "さんぼん"),
+            (4, "本", "よんほん"),
+            (6, "本", "ろっぽん"),
+            (10, "本", "じゅっぽん"),
+            (1, "分", "いっぷん"),
+            (3, "分", "さんぷん"),
+            (1, "個", "いっこ"),
+            (3, "階", "さんがい"),
+            (1, "冊", "いっさつ"),
+            (20, "歳", "はたち"),
+            (21, "歳", "にじゅういっさい"),
+            (1, "人", "ひとり"),
+            (2, "人", "ふたり"),
+            (3, "人", "さんにん"),
+            (4, "人", "よにん"),
+            (14, "人", "じゅうよにん"),
+            (4, "日", "よっか"),
+            (14, "日", "じゅうよっか"),
+            (20, "日", "はつか"),
+            (24, "日", "にじゅうよっか"),
+            (4, "月", "しがつ"),
+            (9, "時", "くじ"),
+            (4, "円", "よえん"),
+            (350, "円", "さんびゃくごじゅうえん"),
+            (3, "つ", "みっつ"),
+            (2, "枚", "にまい"),
+        ] {
+            assert_eq!(read_count(value, counter, ""), reading, "{value}{counter}");
+        }
+        // Counters without known sound changes use the given reading
+        assert_eq!(read_count(3, "羽", "わ"), "さんわ");
+    }
+
+    #[test]
+    fn joins_with_sound_changes() {
+        for (number, counter, euphony, after_n, joined) in [
+            ("いち", "ほん", Euphony::H, AfterN::Voiced, "いっぽん"),
+            ("さん", "ほん", Euphony::H, AfterN::Voiced, "さんぼん"),
+            ("よん", "ほん", Euphony::H, AfterN::Voiced, "よんほん"),
+            ("ろく", "ひゃく", Euphony::H, AfterN::Voiced, "ろっぴゃく"),
+            ("はち", "せん", Euphony::S, AfterN::Voiced, "はっせん"),
+            ("さん", "ふん", Euphony::H, AfterN::SemiVoiced, "さんぷん"),
+            ("じゅう", "こ", Euphony::K, AfterN::Keep, "じゅっこ"),
+            ("ご", "ほん", Euphony::H, AfterN::Voiced, "ごほん"),
+            ("に", "まい", Euphony::None, AfterN::Keep, "にまい"),
+        ] {
+            assert_eq!(
+                join(number, counter, euphony, after_n),
+                joined,
+                "{number}+{counter}"
+            );
+        }
+    }
+
+    #[test]
+    fn finds_numerals_in_sentences() {
+        let numerals = find_numerals(&sentence(vec![
+            token("ビール", "名詞,一般", "*", "*", "ビール", "ビール"),
+            token("を", "助詞,格助詞", "*", "*", "を", "ヲ"),
+            token("三", "名詞,数", "*", "*", "三", "サン"),
+            token("本", "名詞,接尾", "*", "*", "本", "ホン"),
+            token("と", "助詞,並立助詞", "*", "*", "と", "ト"),
+            token("1", "名詞,数", "*", "*", "*", "*"),
+            token(",", "名詞,数", "*", "*", "*", "*"),
+            token("000", "名詞,数", "*", "*", "*", "*"),
+            token("円", "名詞,接尾", "*", "*", "円", "エン"),
+            token("、", "記号,読点", "*", "*", "、", "、"),
+            token("一人", "名詞,一般", "*", "*", "一人", "ヒトリ"),
+        ]));
+        let found: Vec<(&str, u64, Option<&str>, &str)> = numerals
+            .iter()
+            .map(|n| {
+                (
+                    n.surface.as_str(),
+                    n.value,
+                    n.counter.as_deref(),
+                    n.reading.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("三本", 3, Some("本"), "さんぼん"),
+                ("1,000円", 1_000, Some("円"), "せんえん"),
+                ("一人", 1, Some("人"), "ひとり"),
+            ]
+        );
+        assert_eq!((numerals[1].start, numerals[1].end), (5, 9));
+    }
+}
@@ -0,0 +1,587 @@
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Define an enum over dictionary labels
+///
+/// Every variant stands for one label; labels outside the list are kept
+/// verbatim in `Other`, so converting from and back to a string is lossless.
+/// The enum serializes as its label, like the plain strings it replaces.
+macro_rules! label_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $label:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            /// No value: the dictionary's `*`
+            #[default]
+            Unspecified,
+            /// A label not listed above, as given by the dictionary
+            Other(String),
+        }
+
+        impl $name {
+            /// Every known label, without `Unspecified` and `Other`
+            pub const ALL: &[$name] = &[$($name::$variant),*];
+
+            /// Dictionary label, e.g. 名詞
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $label,)*
+                    $name::Unspecified => "*",
+                    $name::Other(label) => label,
+                }
+            }
+
+            /// Whether the dictionary gave a value, i.e. the label is not `*`
+            pub fn is_specified(&self) -> bool {
+                *self != $name::Unspecified
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(label: &str) -> Self {
+                match label {
+                    $($label => $name::$variant,)*
+                    "*" => $name::Unspecified,
+                    other => $name::Other(other.to_string()),
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(label: String) -> Self {
+                $name::from(label.as_str())
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer).map($name::from)
+            }
+        }
+    };
+}
+
+label_enum! {
+    /// Major part-of-speech category (品詞) of IPADIC and UniDic
+    pub enum PosMajor {
+        Noun => "名詞",
+        /// UniDic only; IPADIC files pronouns under 名詞
+        Pronoun => "代名詞",
+        Verb => "動詞",
+        /// い-adjective
+        Adjective => "形容詞",
+        /// な-adjective in UniDic (形状詞)
+        AdjectivalNoun => "形状詞",
+        /// な-adjective in school grammar (形容動詞)
+        AdjectivalVerb => "形容動詞",
+        Adverb => "副詞",
+        /// Pre-noun adjectival (連体詞): この, 大きな
+        Adnominal => "連体詞",
+        Conjunction => "接続詞",
+        Interjection => "感動詞",
+        Particle => "助詞",
+        AuxiliaryVerb => "助動詞",
+        /// IPADIC prefix (接頭詞)
+        Prefix => "接頭詞",
+        /// UniDic prefix (接頭辞)
+        UniDicPrefix => "接頭辞",
+        /// UniDic suffix; IPADIC tags suffixes as a subcategory
+        Suffix => "接尾辞",
+        /// IPADIC symbol
+        Symbol => "記号",
+        /// UniDic punctuation and brackets
+        SupplementarySymbol => "補助記号",
+        /// UniDic whitespace
+        Whitespace => "空白",
+        /// Hesitation: えーと, あの
+        Filler => "フィラー",
+        /// IPADIC's catch-all (その他)
+        Miscellaneous => "その他",
+    }
+}
+
+impl PosMajor {
+    /// Punctuation, brackets and other symbols of either dictionary
+    pub fn is_symbol(&self) -> bool {
+        matches!(self, PosMajor::Symbol | PosMajor::SupplementarySymbol)
+    }
+
+    /// Prefixes of either dictionary
+    pub fn is_prefix(&self) -> bool {
+        matches!(self, PosMajor::Prefix | PosMajor::UniDicPrefix)
+    }
+
+    /// Nouns and pronouns
+    pub fn is_nominal(&self) -> bool {
+        matches!(self, PosMajor::Noun | PosMajor::Pronoun)
+    }
+
+    /// CSS class name for coloring tokens of this category
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            PosMajor::Noun | PosMajor::Pronoun => "pos-noun",
+            PosMajor::Verb => "pos-verb",
+            PosMajor::Adjective | PosMajor::AdjectivalNoun => "pos-adjective",
+            PosMajor::Adverb => "pos-adverb",
+            PosMajor::Particle => "pos-particle",
+            PosMajor::AuxiliaryVerb => "pos-aux-verb",
+            PosMajor::Conjunction => "pos-conjunction",
+            PosMajor::Interjection => "pos-interjection",
+            PosMajor::Adnominal => "pos-adnominal",
+            PosMajor::Symbol | PosMajor::SupplementarySymbol => "pos-symbol",
+            _ => "pos-other",
+        }
+    }
+
+    /// English name, e.g. "Aux. Verb"
+    pub fn english(&self) -> &'static str {
+        match self {
+            PosMajor::Noun => "Noun",
+            PosMajor::Verb => "Verb",
+            PosMajor::Adjective => "i-Adjective",
+            PosMajor::AdjectivalVerb | PosMajor::AdjectivalNoun => "na-Adjective",
+            PosMajor::Pronoun => "Pronoun",
+            PosMajor::Adverb => "Adverb",
+            PosMajor::Particle => "Particle",
+            PosMajor::AuxiliaryVerb => "Aux. Verb",
+            PosMajor::Conjunction => "Conjunction",
+            PosMajor::Interjection => "Interjection",
+            PosMajor::Adnominal => "Adnominal",
+            PosMajor::Symbol | PosMajor::SupplementarySymbol => "Symbol",
+            PosMajor::UniDicPrefix => "Prefix",
+            PosMajor::Suffix => "Suffix",
+            PosMajor::Filler => "Filler",
+            _ => "Other",
+        }
+    }
+}
+
+label_enum! {
+    /// Part-of-speech subcategory (品詞細分類) at any of the three levels
+    ///
+    /// IPADIC and UniDic share some labels, like 一般 and 固有名詞; others
+    /// exist in one dictionary only.
+    pub enum PosSub {
+        General => "一般",
+        ProperNoun => "固有名詞",
+        Pronoun => "代名詞",
+        Number => "数",
+        /// Noun that takes する: 勉強
+        SuruNoun => "サ変接続",
+        /// Stem of a な-adjective: 静か
+        NaAdjectiveStem => "形容動詞語幹",
+        /// Noun also used as an adverb: 今日
+        Adverbial => "副詞可能",
+        /// Stem followed by ない: 違い in 違いない
+        NaiAdjectiveStem => "ナイ形容詞語幹",
+        /// Not independent: こと, いる in ている
+        Dependent => "非自立",
+        Suffix => "接尾",
+        Special => "特殊",
+        QuotedString => "引用文字列",
+        /// Noun that behaves like a dependent verb
+        VerbDependent => "動詞非自立的",
+        /// Noun used like a conjunction
+        ConjunctionLike => "接続詞的",
+        Independent => "自立",
+        /// Adverb that takes a particle
+        ParticleConnecting => "助詞類接続",
+        CaseParticle => "格助詞",
+        BindingParticle => "係助詞",
+        AdverbialParticle => "副助詞",
+        SentenceFinalParticle => "終助詞",
+        ConjunctiveParticle => "接続助詞",
+        ParallelParticle => "並立助詞",
+        /// の linking nouns
+        Adnominalizer => "連体化",
+        /// に turning words into adverbs
+        Adverbializer => "副詞化",
+        /// Particles like か that play several roles
+        AdverbialParallelFinalParticle => "副助詞／並立助詞／終助詞",
+        NounConnecting => "名詞接続",
+        VerbConnecting => "動詞接続",
+        AdjectiveConnecting => "形容詞接続",
+        NumberConnecting => "数接続",
+        /// 。
+        Period => "句点",
+        /// 、
+        Comma => "読点",
+        Space => "空白",
+        OpeningBracket => "括弧開",
+        ClosingBracket => "括弧閉",
+        Alphabet => "アルファベット",
+        PersonName => "人名",
+        Region => "地域",
+        Organization => "組織",
+        Quotation => "引用",
+        /// Multi-word expression
+        Compound => "連語",
+        /// Counter word: 人, 本, 冊
+        Counter => "助数詞",
+        AuxiliaryVerbStem => "助動詞語幹",
+        Surname => "姓",
+        GivenName => "名",
+        Country => "国",
+        /// UniDic common noun
+        CommonNoun => "普通名詞",
+        /// UniDic numeral
+        Numeral => "数詞",
+        /// UniDic word that may be dependent
+        PossiblyDependent => "非自立可能",
+        /// UniDic noun that takes する
+        SuruPossible => "サ変可能",
+        /// UniDic noun that may be a な-adjective
+        NaAdjectivePossible => "形状詞可能",
+        /// UniDic nominalizing particle: の in 行くのが
+        NominalizingParticle => "準体助詞",
+        PlaceName => "地名",
+        NounLike => "名詞的",
+        AdjectiveLike => "形容詞的",
+        VerbLike => "動詞的",
+        NaAdjectiveLike => "形状詞的",
+        /// UniDic タリ adjective: 堂々
+        Tari => "タリ",
+        /// UniDic single character
+        Character => "文字",
+        Emoticon => "顔文字",
+    }
+}
+
+impl PosSub {
+    /// Particles that can end a sentence: よ, ね, か
+    pub fn is_sentence_final(&self) -> bool {
+        matches!(
+            self,
+            PosSub::SentenceFinalParticle | PosSub::AdverbialParallelFinalParticle
+        )
+    }
+
+    /// Nouns that form verbs with する, in either dictionary
+    pub fn takes_suru(&self) -> bool {
+        matches!(self, PosSub::SuruNoun | PosSub::SuruPossible)
+    }
+}
+
+label_enum! {
+    /// Conjugation type (活用型) of IPADIC
+    ///
+    /// UniDic's types are kept in `Other`.
+    pub enum ConjugationType {
+        Ichidan => "一段",
+        /// くれる: imperative くれ
+        IchidanKureru => "一段・クレル",
+        /// 得る
+        IchidanEru => "一段・得ル",
+        /// 書く: 書いて
+        GodanKaIOnbin => "五段・カ行イ音便",
+        /// 行く: 行って
+        GodanKaSokuonbin => "五段・カ行促音便",
+        /// ゆく: ゆいて
+        GodanKaSokuonbinYuku => "五段・カ行促音便ユク",
+        GodanGa => "五段・ガ行",
+        GodanSa => "五段・サ行",
+        GodanTa => "五段・タ行",
+        GodanNa => "五段・ナ行",
+        GodanBa => "五段・バ行",
+        GodanMa => "五段・マ行",
+        GodanRa => "五段・ラ行",
+        /// ある: negative ない
+        GodanRaAru => "五段・ラ行アル",
+        /// いらっしゃる, なさる, くださる
+        GodanRaSpecial => "五段・ラ行特殊",
+        /// 問う: 問うて
+        GodanWaUOnbin => "五段・ワ行ウ音便",
+        /// 買う: 買って
+        GodanWaSokuonbin => "五段・ワ行促音便",
+        /// 来る written in kanji
+        KahenKanji => "カ変・来ル",
+        /// くる written in kana
+        KahenKana => "カ変・クル",
+        /// する
+        SahenSuru => "サ変・スル",
+        /// A single kanji and する: 愛する, 察する
+        SahenSuffixSuru => "サ変・－スル",
+        /// Compounds in ずる: 信ずる
+        SahenZuru => "サ変・－ズル",
+        /// Adjectives in -ai, -ui, -oi: 高い, 寒い
+        AdjectiveAuo => "形容詞・アウオ段",
+        /// Adjectives in -ii: 美しい
+        AdjectiveI => "形容詞・イ段",
+        /// いい
+        AdjectiveIi => "形容詞・イイ",
+        SpecialDa => "特殊・ダ",
+        SpecialDesu => "特殊・デス",
+        SpecialTa => "特殊・タ",
+        SpecialTai => "特殊・タイ",
+        SpecialNai => "特殊・ナイ",
+        SpecialNu => "特殊・ヌ",
+        SpecialMasu => "特殊・マス",
+        SpecialYa => "特殊・ヤ",
+        SpecialJa => "特殊・ジャ",
+        /// Words that do not inflect, like the auxiliary う
+        Invariant => "不変化型",
+        /// Classical 四段 verbs: 思ふ
+        YodanKa => "四段・カ行",
+        YodanGa => "四段・ガ行",
+        YodanSa => "四段・サ行",
+        YodanTa => "四段・タ行",
+        YodanHa => "四段・ハ行",
+        YodanBa => "四段・バ行",
+        YodanMa => "四段・マ行",
+        YodanRa => "四段・ラ行",
+        /// Classical 下二段 verbs: 受く, 見ゆ
+        ShimoNidanA => "下二・ア行",
+        ShimoNidanKa => "下二・カ行",
+        ShimoNidanGa => "下二・ガ行",
+        ShimoNidanSa => "下二・サ行",
+        ShimoNidanZa => "下二・ザ行",
+        ShimoNidanTa => "下二・タ行",
+        ShimoNidanDa => "下二・ダ行",
+        ShimoNidanNa => "下二・ナ行",
+        ShimoNidanHa => "下二・ハ行",
+        ShimoNidanBa => "下二・バ行",
+        ShimoNidanMa => "下二・マ行",
+        ShimoNidanYa => "下二・ヤ行",
+        ShimoNidanRa => "下二・ラ行",
+        ShimoNidanWa => "下二・ワ行",
+        /// 得 (う), as in あり得る
+        ShimoNidanEru => "下二・得",
+        /// Classical 上二段 verbs: 恥づ, 恋ふ
+        KamiNidanDa => "上二・ダ行",
+        KamiNidanHa => "上二・ハ行",
+        /// Classical あり and its compounds
+        Rahen => "ラ変",
+        /// Classical auxiliaries: き, けり, ごとし, なり, べし, まじ, り, る
+        ClassicalKi => "文語・キ",
+        ClassicalKeri => "文語・ケリ",
+        ClassicalGotoshi => "文語・ゴトシ",
+        ClassicalNari => "文語・ナリ",
+        ClassicalBeshi => "文語・ベシ",
+        ClassicalMaji => "文語・マジ",
+        ClassicalRi => "文語・リ",
+        ClassicalRu => "文語・ル",
+    }
+}
+
+impl ConjugationType {
+    pub fn is_godan(&self) -> bool {
+        matches!(
+            self,
+            ConjugationType::GodanKaIOnbin
+                | ConjugationType::GodanKaSokuonbin
+                | ConjugationType::GodanKaSokuonbinYuku
+                | ConjugationType::GodanGa
+                | ConjugationType::GodanSa
+                | ConjugationType::GodanTa
+                | ConjugationType::GodanNa
+                | ConjugationType::GodanBa
+                | ConjugationType::GodanMa
+                | ConjugationType::GodanRa
+                | ConjugationType::GodanRaAru
+                | ConjugationType::GodanRaSpecial
+                | ConjugationType::GodanWaUOnbin
+                | ConjugationType::GodanWaSokuonbin
+        )
+    }
+
+    pub fn is_ichidan(&self) -> bool {
+        matches!(
+            self,
+            ConjugationType::Ichidan | ConjugationType::IchidanKureru | ConjugationType::IchidanEru
+        )
+    }
+
+    /// 来る
+    pub fn is_kahen(&self) -> bool {
+        matches!(
+            self,
+            ConjugationType::KahenKanji | ConjugationType::KahenKana
+        )
+    }
+
+    /// する and its compounds
+    pub fn is_sahen(&self) -> bool {
+        matches!(
+            self,
+            ConjugationType::SahenSuru
+                | ConjugationType::SahenSuffixSuru
+                | ConjugationType::SahenZuru
+        )
+    }
+
+    /// Types of classical Japanese (文語), which are not conjugated here
+    pub fn is_classical(&self) -> bool {
+        matches!(
+            self,
+            ConjugationType::YodanKa
+                | ConjugationType::YodanGa
+                | ConjugationType::YodanSa
+                | ConjugationType::YodanTa
+                | ConjugationType::YodanHa
+                | ConjugationType::YodanBa
+                | ConjugationType::YodanMa
+                | ConjugationType::YodanRa
+                | ConjugationType::ShimoNidanA
+                | ConjugationType::ShimoNidanKa
+                | ConjugationType::ShimoNidanGa
+                | ConjugationType::ShimoNidanSa
+                | ConjugationType::ShimoNidanZa
+                | ConjugationType::ShimoNidanTa
+                | ConjugationType::ShimoNidanDa
+                | ConjugationType::ShimoNidanNa
+                | ConjugationType::ShimoNidanHa
+                | ConjugationType::ShimoNidanBa
+                | ConjugationType::ShimoNidanMa
+                | ConjugationType::ShimoNidanYa
+                | ConjugationType::ShimoNidanRa
+                | ConjugationType::ShimoNidanWa
+                | ConjugationType::ShimoNidanEru
+                | ConjugationType::KamiNidanDa
+                | ConjugationType::KamiNidanHa
+                | ConjugationType::Rahen
+                | ConjugationType::ClassicalKi
+                | ConjugationType::ClassicalKeri
+                | ConjugationType::ClassicalGotoshi
+                | ConjugationType::ClassicalNari
+                | ConjugationType::ClassicalBeshi
+                | ConjugationType::ClassicalMaji
+                | ConjugationType::ClassicalRi
+                | ConjugationType::ClassicalRu
+        )
+    }
+
+    /// い-adjectives
+    pub fn is_adjective(&self) -> bool {
+        matches!(
+            self,
+            ConjugationType::AdjectiveAuo
+                | ConjugationType::AdjectiveI
+                | ConjugationType::AdjectiveIi
+        )
+    }
+}
+
+label_enum! {
+    /// Conjugation form (活用形) of IPADIC
+    ///
+    /// UniDic's forms, like 連用形-一般, are kept in `Other`.
+    pub enum ConjugationForm {
+        /// Dictionary form
+        Basic => "基本形",
+        /// Irrealis: 書か in 書かない
+        Irrealis => "未然形",
+        /// Irrealis before う: 書こ in 書こう
+        IrrealisU => "未然ウ接続",
+        /// Irrealis before ぬ: せ in せぬ
+        IrrealisNu => "未然ヌ接続",
+        /// Irrealis before れる: さ in される
+        IrrealisReru => "未然レル接続",
+        IrrealisSpecial => "未然特殊",
+        /// Continuative: 書き in 書きます
+        Continuative => "連用形",
+        /// Continuative before た: 書い in 書いた
+        ContinuativeTa => "連用タ接続",
+        /// Continuative before て: 高く in 高くて
+        ContinuativeTe => "連用テ接続",
+        /// Continuative before ございます: 高う
+        ContinuativeGozai => "連用ゴザイ接続",
+        /// Continuative before で: だっ in で
+        ContinuativeDe => "連用デ接続",
+        /// Continuative before に
+        ContinuativeNi => "連用ニ接続",
+        /// Hypothetical: 書け in 書けば
+        Hypothetical => "仮定形",
+        /// Contracted hypothetical: 書きゃ
+        HypotheticalContracted1 => "仮定縮約１",
+        HypotheticalContracted2 => "仮定縮約２",
+        /// Imperative in -e: 書け
+        ImperativeE => "命令ｅ",
+        ImperativeI => "命令ｉ",
+        /// Imperative in -ろ: 食べろ
+        ImperativeRo => "命令ｒｏ",
+        /// Imperative in -よ: 食べよ
+        ImperativeYo => "命令ｙｏ",
+        /// Attributive, before a noun
+        Attributive => "体言接続",
+        AttributiveSpecial => "体言接続特殊",
+        AttributiveSpecial2 => "体言接続特殊２",
+        /// Euphonic dictionary form: 高う
+        EuphonicBasic => "音便基本形",
+        /// Classical dictionary form: 高し
+        ClassicalBasic => "文語基本形",
+        /// Stem before がる: 寒 in 寒がる
+        GaruConnecting => "ガル接続",
+        ModernBasic => "現代基本形",
+    }
+}
+
+impl ConjugationForm {
+    /// Dictionary forms (基本形)
+    pub fn is_basic(&self) -> bool {
+        matches!(
+            self,
+            ConjugationForm::Basic
+                | ConjugationForm::EuphonicBasic
+                | ConjugationForm::ClassicalBasic
+                | ConjugationForm::ModernBasic
+        )
+    }
+
+    /// Irrealis forms (未然), as before ない
+    pub fn is_irrealis(&self) -> bool {
+        match self {
+            ConjugationForm::Irrealis
+            | ConjugationForm::IrrealisU
+            | ConjugationForm::IrrealisNu
+            | ConjugationForm::IrrealisReru
+            | ConjugationForm::IrrealisSpecial => true,
+            ConjugationForm::Other(form) => form.starts_with("未然"),
+            _ => false,
+        }
+    }
+
+    /// Continuative forms (連用), as before ます
+    pub fn is_continuative(&self) -> bool {
+        match self {
+            ConjugationForm::Continuative
+            | ConjugationForm::ContinuativeTa
+            | ConjugationForm::ContinuativeTe
+            | ConjugationForm::ContinuativeGozai
+            | ConjugationForm::ContinuativeDe
+            | ConjugationForm::ContinuativeNi => true,
+            ConjugationForm::Other(form) => form.starts_with("連用"),
+            _ => false,
+        }
+    }
+
+    /// Forms that can modify a noun: dictionary and attributive forms,
+    /// including UniDic's 連体形
+    pub fn is_attributive(&self) -> bool {
+        match self {
+            ConjugationForm::Attributive
+            | ConjugationForm::AttributiveSpecial
+            | ConjugationForm::AttributiveSpecial2 => true,
+            ConjugationForm::Other(form) => form.starts_with("連体形"),
+            _ => self.is_basic(),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use serde::{Deserialize, Serialize};
+
+use crate::pos::PosMajor;
+use crate::tokenizer::{AnalyzedSentence, AnalyzedToken};
+
+/// Verbs that are respectful (尊敬語) on their own
+const RESPECTFUL_VERBS: &[&str] = &[
+    "いらっしゃる",
+    "おっしゃる",
+    "仰る",
+    "召し上がる",
+    "なさる",
+    "為さる",
+    "ご覧",
+    "御覧",
+];
+
+/// Verbs that are humble (謙譲語) on their own
+const HUMBLE_VERBS: &[&str] = &[
+    "参る",
+    "申す",
+    "申し上げる",
+    "いたす",
+    "致す",
+    "伺う",
+    "おる",
+    "存じる",
+    "存ずる",
+    "拝見",
+    "いただく",
+    "頂く",
+    "差し上げる",
+    "承る",
+];
+
+/// Politeness register of an utterance
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Register {
+    /// Plain form (普通体)
+    Plain,
+    /// です/ます (丁寧語)
+    Polite,
+    /// Raises the listener or a third person (尊敬語)
+    Respectful,
+    /// Lowers the speaker (謙譲語)
+    Humble,
+}
+
+impl Register {
+    pub const ALL: [Register; 4] = [
+        Register::Plain,
+        Register::Polite,
+        Register::Respectful,
+        Register::Humble,
+    ];
+
+    /// Identifier used in form values and CSS classes, e.g. `polite`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Register::Plain => "plain",
+            Register::Polite => "polite",
+            Register::Respectful => "respectful",
+            Register::Humble => "humble",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Register::Plain => "Plain",
+            Register::Polite => "Polite",
+            Register::Respectful => "Respectful",
+            Register::Humble => "Humble",
+        }
+    }
+
+    pub fn label_ja(&self) -> &'static str {
+        match self {
+            Register::Plain => "普通体",
+            Register::Polite => "丁寧語",
+            Register::Respectful => "尊敬語",
+            Register::Humble => "謙譲語",
+        }
+    }
+}
+
+/// Classify the politeness register of an analyzed sentence
+///
+/// Keigo is recognized from honorific verbs (いらっしゃる, 申す, …) and from
+/// the お/ご patterns: お待ちになる and お待ちください are respectful, お願いする
+/// is humble. A sentence with both kinds counts as respectful. Without keigo,
+/// a です or ます makes the sentence polite. The respectful れる/られる is not
+/// recognized, since it cannot be told apart from the passive.
+pub fn classify_register(sentence: &AnalyzedSentence) -> Register {
+    let tokens = &sentence.tokens;
+    let mut humble = false;
+    let mut polite = false;
+
+    for (index, token) in tokens.iter().enumerate() {
+        match keigo_at(tokens, index) {
+            Some(Register::Respectful) => return Register::Respectful,
+            Some(Register::Humble) => humble = true,
+            _ => {}
+        }
+        polite |=
+            token.pos.major == PosMajor::AuxiliaryVerb && matches!(token.base_form.as_str(), "です" | "ます");
+    }
+
+    if humble {
+        Register::Humble
+    } else if polite {
+        Register::Polite
+    } else {
+        Register::Plain
+    }
+}
+
+/// Keigo register marked by the token at `index`, if any
+fn keigo_at(tokens: &[AnalyzedToken], index: usize) -> Option<Register> {
+    let token = &tokens[index];
+    let lemma = if token.base_form == "*" {
+        token.surface.as_str()
+    } else {
+        token.base_form.as_str()
+    };
+    let verb_like = matches!(token.pos.major, PosMajor::Verb | PosMajor::Noun);
+    if verb_like && RESPECTFUL_VERBS.contains(&lemma) {
+        return Some(Register::Respectful);
+    }
+    if verb_like && HUMBLE_VERBS.contains(&lemma) {
+        return Some(Register::Humble);
+    }
+
+    // お/ご + stem + になる / ください / する
+    let is_prefix = token.pos.major.is_prefix()
+        && matches!(token.surface.as_str(), "お" | "ご" | "御");
+    let stem = tokens.get(index + 1)?;
+    if !is_prefix || !(is_verb_stem(stem) || stem.pos.major == PosMajor::Noun) {
+        return None;
+    }
+    let next = |offset: usize| tokens.get(index + 2 + offset).map(|t| t.base_form.as_str());
+    match next(0)? {
+        "に" if next(1) == Some("なる") && (is_verb_stem(stem) || is_suru_noun(stem)) => {
+            Some(Register::Respectful)
+        }
+        "くださる" | "下さる" => Some(Register::Respectful),
+        "する" | "いたす" | "致す" | "申し上げる" => Some(Register::Humble),
+        _ => None,
+    }
+}
+
+fn is_verb_stem(token: &AnalyzedToken) -> bool {
+    token.pos.major == PosMajor::Verb && token.conjugation_form.is_continuative()
+}
+
+/// Nouns that take する, e.g. 連絡 (IPADIC サ変接続, UniDic サ変可能)
+fn is_suru_noun(token: &AnalyzedToken) -> bool {
+    token.pos.major == PosMajor::Noun && (token.pos.sub1.takes_suru() || token.pos.sub2.takes_suru())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sentence, token};
+
+    fn t(surface: &str, pos: &str, form: &str, base: &str) -> AnalyzedToken {
+        token(surface, pos, "*", form, base, "*")
+    }
+
+    fn masu() -> AnalyzedToken {
+        t("ます", "助動詞", "基本形", "ます")
+    }
+
+    fn register(tokens: Vec<AnalyzedToken>) -> Register {
+        classify_register(&sentence(tokens))
+    }
+
+    #[test]
+    fn plain_and_polite() {
+        assert_eq!(register(vec![t("行く", "動詞,自立", "基本形", "行く")]), Register::Plain);
+        assert_eq!(
+            register(vec![t("行き", "動詞,自立", "連用形", "行く"), masu()]),
+            Register::Polite
+        );
+        assert_eq!(
+            register(vec![
+                t("学生", "名詞,一般", "*", "学生"),
+                t("です", "助動詞", "基本形", "です"),
+            ]),
+            Register::Polite
+        );
+        // です as a noun is not the copula
+        assert_eq!(register(vec![t("です", "名詞,一般", "*", "です")]), Register::Plain);
+    }
+
+    #[test]
+    fn honorific_verbs() {
+        let cases = [
+            ("いらっしゃい", "いらっしゃる", Register::Respectful),
+            ("おっしゃい", "おっしゃる", Register::Respectful),
+            ("召し上がり", "召し上がる", Register::Respectful),
+            ("参り", "参る", Register::Humble),
+            ("申し", "申す", Register::Humble),
+            ("伺い", "伺う", Register::Humble),
+        ];
+        for (surface, base, expected) in cases {
+            let tokens = vec![t(surface, "動詞,自立", "連用形", base), masu()];
+            assert_eq!(register(tokens), expected, "{base}");
+        }
+    }
+
+    #[test]
+    fn o_stem_ni_naru() {
+        // お待ちになる
+        let tokens = vec![
+            t("お", "接頭詞,動詞接続", "*", "お"),
+            t("待ち", "動詞,自立", "連用形", "待つ"),
+            t("に", "助詞,格助詞", "*", "に"),
+            t("なる", "動詞,自立", "基本形", "なる"),
+        ];
+        assert_eq!(register(tokens), Register::Respectful);
+
+        // ご連絡になる: a サ変 noun works as the stem
+        let tokens = vec![
+            t("ご", "接頭詞,名詞接続", "*", "ご"),
+            t("連絡", "名詞,サ変接続", "*", "連絡"),
+            t("に", "助詞,格助詞", "*", "に"),
+            t("なる", "動詞,自立", "基本形", "なる"),
+        ];
+        assert_eq!(register(tokens), Register::Respectful);
+
+        // お茶になる is not keigo
+        let tokens = vec![
+            t("お", "接頭詞,名詞接続", "*", "お"),
+            t("茶", "名詞,一般", "*", "茶"),
+            t("に", "助詞,格助詞", "*", "に"),
+            t("なる", "動詞,自立", "基本形", "なる"),
+        ];
+        assert_eq!(register(tokens), Register::Plain);
+    }
+
+    #[test]
+    fn o_stem_kudasai() {
+        let ipadic = vec![
+            t("お", "接頭詞,動詞接続", "*", "お"),
+            t("待ち", "動詞,自立", "連用形", "待つ"),
+            t("ください", "動詞,非自立", "命令ｉ", "くださる"),
+        ];
+        assert_eq!(register(ipadic), Register::Respectful);
+
+        let unidic = vec![
+            t("お", "接頭辞", "*", "お"),
+            t("待ち", "動詞,一般", "連用形-一般", "待つ"),
+            t("ください", "動詞,非自立可能", "命令形", "下さる"),
+        ];
+        assert_eq!(register(unidic), Register::Respectful);
+    }
+
+    #[test]
+    fn o_stem_suru() {
+        // お願いします
+        let tokens = vec![
+            t("お", "接頭詞,動詞接続", "*", "お"),
+            t("願い", "動詞,自立", "連用形", "願う"),
+            t("し", "動詞,自立", "連用形", "する"),
+            masu(),
+        ];
+        assert_eq!(register(tokens), Register::Humble);
+    }
+
+    #[test]
+    fn respectful_over_humble() {
+        let humble = t("参り", "動詞,自立", "連用形", "参る");
+        let respectful = t("いらっしゃい", "動詞,自立", "連用形", "いらっしゃる");
+        let comma = t("、", "記号,読点", "*", "、");
+        assert_eq!(
+            register(vec![humble.clone(), masu(), comma.clone(), respectful.clone(), masu()]),
+            Register::Respectful
+        );
+        assert_eq!(
+            register(vec![respectful, masu(), comma, humble, masu()]),
+            Register::Respectful
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+
+/// Characters that end a sentence; runs of them (？！) end it together
+const TERMINATORS: &[char] = &['。', '！', '？', '!', '?', '．'];
+/// Ellipsis characters; `...` counts as well
+const ELLIPSES: &[char] = &['…', '‥'];
+/// Opening brackets, inside which terminators do not split
+const OPENERS: &[char] = &['「', '『', '（', '(', '【', '〈', '《', '〔', '［', '“'];
+/// Closing brackets and quotes that stay with the sentence they follow
+const CLOSERS: &[char] = &['」', '』', '）', ')', '】', '〉', '》', '〕', '］', '”'];
+/// Quotative continuations: in すごい！って思った the ！ does not end the sentence
+const QUOTATIVES: &[&str] = &["って", "と言", "といっ", "と思", "とおも", "と聞", "と答"];
+/// Sentence-final particles that end an unpunctuated sentence before whitespace
+const FINAL_PARTICLES: &[&str] =
+    &["ね", "よ", "わ", "ぞ", "ぜ", "さ", "な", "か", "っけ", "かしら"];
+/// Lengthening marks that may follow a final particle: そうだねー
+const LENGTHENERS: &[char] = &['ー', '〜', '～'];
+
+/// One sentence of a text
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SentenceSpan {
+    /// Sentence text without surrounding whitespace
+    pub text: String,
+    /// Byte offset of the sentence in the text
+    pub byte_start: usize,
+    /// Byte offset one past the sentence in the text
+    pub byte_end: usize,
+    /// Char offset of the sentence in the text
+    pub char_start: usize,
+    /// Char offset one past the sentence in the text
+    pub char_end: usize,
+}
+
+/// Split Japanese text into sentences
+///
+/// A sentence ends at a run of 。！？, together with any closing quotes and
+/// brackets right after it, and at line breaks. Terminators inside 「」 and
+/// other brackets do not split, nor do ones followed by a quotative って or
+/// と言う. An ellipsis or a sentence-final particle like ね ends a sentence
+/// only when whitespace follows it, as in そうだね　行こう.
+///
+/// A bracket counts only when it is closed on the same line, so a stray 「
+/// does not swallow the sentences after it.
+pub fn split_sentences(text: &str) -> Vec<SentenceSpan> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let rest = |index: usize| chars.get(index).map_or("", |&(byte, _)| &text[byte..]);
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index].1;
+        if OPENERS.contains(&c) && closed_on_line(&chars[index + 1..]) {
+            depth += 1;
+        } else if CLOSERS.contains(&c) {
+            depth = depth.saturating_sub(1);
+        }
+
+        let break_after = if c == '\n' {
+            depth = 0;
+            Some(index + 1)
+        } else if depth > 0 {
+            None
+        } else if c.is_whitespace() {
+            ends_with_final_particle(&text[..chars[index].0]).then_some(index + 1)
+        } else if TERMINATORS.contains(&c) {
+            let mut end = index + 1;
+            while end < chars.len()
+                && (TERMINATORS.contains(&chars[end].1)
+                    || ELLIPSES.contains(&chars[end].1)
+                    || CLOSERS.contains(&chars[end].1))
+            {
+                end += 1;
+            }
+            let quoted = QUOTATIVES.iter().any(|q| rest(end).starts_with(q));
+            (!quoted).then_some(end)
+        } else if ELLIPSES.contains(&c) || rest(index).starts_with("...") {
+            let mut end = index + 1;
+            while end < chars.len() && (ELLIPSES.contains(&chars[end].1) || chars[end].1 == '.') {
+                end += 1;
+            }
+            let spaced = chars.get(end).is_some_and(|&(_, next)| next.is_whitespace());
+            if spaced {
+                Some(end)
+            } else {
+                index = end;
+                continue;
+            }
+        } else {
+            None
+        };
+
+        match break_after {
+            Some(end) => {
+                push_sentence(&mut sentences, text, &chars, start, end);
+                start = end;
+                index = end;
+            }
+            None => index += 1,
+        }
+    }
+    push_sentence(&mut sentences, text, &chars, start, chars.len());
+    sentences
+}
+
+/// Whether a closing bracket comes before the next line break
+fn closed_on_line(after: &[(usize, char)]) -> bool {
+    after
+        .iter()
+        .map(|&(_, c)| c)
+        .take_while(|&c| c != '\n')
+        .any(|c| CLOSERS.contains(&c))
+}
+
+/// Whether `before` ends in a sentence-final particle, possibly lengthened
+fn ends_with_final_particle(before: &str) -> bool {
+    let before = before.trim_end_matches(LENGTHENERS);
+    FINAL_PARTICLES.iter().any(|p| before.ends_with(p))
+}
+
+/// Add the chars `start..end` as a sentence, trimmed of whitespace
+fn push_sentence(
+    sentences: &mut Vec<SentenceSpan>,
+    text: &str,
+    chars: &[(usize, char)],
+    start: usize,
+    end: usize,
+) {
+    let Some(first) = (start..end).find(|&i| !chars[i].1.is_whitespace()) else {
+        return;
+    };
+    let last = (first..end)
+        .rfind(|&i| !chars[i].1.is_whitespace())
+        .unwrap_or(first);
+    let byte_start = chars[first].0;
+    let byte_end = chars.get(last + 1).map_or(text.len(), |&(byte, _)| byte);
+    sentences.push(SentenceSpan {
+        text: text[byte_start..byte_end].to_string(),
+        byte_start,
+        byte_end,
+        char_start: first,
+        char_end: last + 1,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(text: &str) -> Vec<String> {
+        split_sentences(text).into_iter().map(|s| s.text).collect()
+    }
+
+    #[test]
+    fn terminators() {
+        assert_eq!(
+            texts("おはようございます。今日はいい天気ですね。"),
+            ["おはようございます。", "今日はいい天気ですね。"]
+        );
+        assert_eq!(texts("本当？！すごい"), ["本当？！", "すごい"]);
+        assert_eq!(texts("一行目\n二行目"), ["一行目", "二行目"]);
+        assert!(texts("").is_empty());
+    }
+
+    #[test]
+    fn quotes_and_quotatives() {
+        assert_eq!(
+            texts("「行こう。早く！」と言った。それで"),
+            ["「行こう。早く！」と言った。", "それで"]
+        );
+        assert_eq!(texts("彼は「はい。」"), ["彼は「はい。」"]);
+        assert_eq!(texts("すごい！って思った。"), ["すごい！って思った。"]);
+    }
+
+    #[test]
+    fn unclosed_brackets_do_not_swallow_the_text() {
+        assert_eq!(texts("「すごい。行こう。"), ["「すごい。", "行こう。"]);
+        assert_eq!(
+            texts("（笑。\nまた明日。じゃあね。"),
+            ["（笑。", "また明日。", "じゃあね。"]
+        );
+        assert_eq!(
+            texts("「ちょっと\n待って。」行こう。"),
+            ["「ちょっと", "待って。」", "行こう。"]
+        );
+    }
+
+    #[test]
+    fn ellipses() {
+        assert_eq!(texts("えっと… 行こう"), ["えっと…", "行こう"]);
+        assert_eq!(texts("えっと…行こう"), ["えっと…行こう"]);
+        assert_eq!(texts("待って... 今行く"), ["待って...", "今行く"]);
+    }
+
+    #[test]
+    fn final_particles_before_whitespace() {
+        assert_eq!(texts("そうだね　行こう"), ["そうだね", "行こう"]);
+        assert_eq!(texts("そうだねー 行こうか"), ["そうだねー", "行こうか"]);
+        assert_eq!(texts("本当かしら　まあいいや"), ["本当かしら", "まあいいや"]);
+        assert_eq!(texts("今日は 晴れ"), ["今日は 晴れ"]);
+        assert_eq!(texts("そうだね　"), ["そうだね"]);
+    }
+
+    #[test]
+    fn offsets_point_into_the_text() {
+        let text = " はい。　そうだね いいえ";
+        let spans = split_sentences(text);
+        assert_eq!(spans.len(), 3);
+        for span in &spans {
+            assert_eq!(&text[span.byte_start..span.byte_end], span.text);
+            let chars: String = text
+                .chars()
+                .skip(span.char_start)
+                .take(span.char_end - span.char_start)
+                .collect();
+            assert_eq!(chars, span.text);
+        }
+        assert_eq!((spans[0].char_start, spans[0].char_end), (1, 4));
+    }
+}
@@ -0,0 +1,472 @@
+use serde::{Deserialize, Serialize};
+
+use crate::conjugation::{ConjugationClass, Paradigm, ParadigmForm, conjugate, token_paradigm};
+use crate::pos::{ConjugationForm, ConjugationType, PosMajor};
+use crate::tokenizer::{AnalyzedSentence, AnalyzedToken};
+
+/// Sentence-final style a sentence can be converted to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum SpeechStyle {
+    /// Plain form (普通体): 食べる, 学生だ
+    Plain,
+    /// です/ます form (丁寧体): 食べます, 学生です
+    Polite,
+}
+
+impl SpeechStyle {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SpeechStyle::Plain => "Plain",
+            SpeechStyle::Polite => "Polite",
+        }
+    }
+
+    pub fn label_ja(&self) -> &'static str {
+        match self {
+            SpeechStyle::Plain => "普通体",
+            SpeechStyle::Polite => "丁寧体",
+        }
+    }
+}
+
+/// A rewritten predicate
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StyleChange {
+    /// Index of the first replaced token
+    pub start: usize,
+    /// Index one past the last replaced token
+    pub end: usize,
+    pub original: String,
+    pub converted: String,
+}
+
+/// A sentence rewritten into another style
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StyleConversion {
+    pub style: SpeechStyle,
+    /// Token surfaces joined with the changes applied
+    pub text: String,
+    /// Changes in token order; empty when the sentence already had the style
+    /// or its predicates were not recognized
+    pub changes: Vec<StyleChange>,
+}
+
+/// Rewrite the sentence-final predicates of `sentence` into `style`
+///
+/// Only predicates that end a sentence, before any sentence-final particles
+/// and punctuation, are rewritten; predicates inside clauses keep their form.
+/// Keigo is left alone: お待ちください stays as it is in the plain style.
+pub fn convert_style(sentence: &AnalyzedSentence, style: SpeechStyle) -> StyleConversion {
+    let tokens = &sentence.tokens;
+    let changes: Vec<StyleChange> = predicate_ends(tokens)
+        .into_iter()
+        .filter_map(|end| {
+            let (start, converted) = match style {
+                SpeechStyle::Plain => to_plain(tokens, end)?,
+                SpeechStyle::Polite => to_polite(tokens, end)?,
+            };
+            Some(StyleChange {
+                start,
+                end: end + 1,
+                original: tokens[start..=end]
+                    .iter()
+                    .map(|t| t.surface.as_str())
+                    .collect(),
+                converted,
+            })
+        })
+        .collect();
+
+    let mut text = String::new();
+    let mut index = 0;
+    for change in &changes {
+        text.extend(
+            tokens[index..change.start]
+                .iter()
+                .map(|t| t.surface.as_str()),
+        );
+        text.push_str(&change.converted);
+        index = change.end;
+    }
+    text.extend(tokens[index..].iter().map(|t| t.surface.as_str()));
+
+    StyleConversion {
+        style,
+        text,
+        changes,
+    }
+}
+
+/// Index of the last token of each sentence's predicate
+fn predicate_ends(tokens: &[AnalyzedToken]) -> Vec<usize> {
+    let mut ends = Vec::new();
+    let mut sentence_start = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        let last = index + 1 == tokens.len();
+        if !(is_sentence_break(token) || last) {
+            continue;
+        }
+        if let Some(end) = (sentence_start..=index)
+            .rev()
+            .find(|&i| !is_trailing(&tokens[i]))
+        {
+            ends.push(end);
+        }
+        sentence_start = index + 1;
+    }
+    ends
+}
+
+fn is_sentence_break(token: &AnalyzedToken) -> bool {
+    matches!(token.surface.as_str(), "。" | "！" | "？" | "!" | "?")
+}
+
+/// Punctuation and sentence-final particles (よ, ね, か, …)
+fn is_trailing(token: &AnalyzedToken) -> bool {
+    token.pos.major.is_symbol()
+        || (token.pos.major == PosMajor::Particle && token.pos.sub1.is_sentence_final())
+}
+
+/// Start of the polite predicate ending at `end` and its plain replacement
+fn to_plain(tokens: &[AnalyzedToken], end: usize) -> Option<(usize, String)> {
+    let at = |offset: usize| end.checked_sub(offset).map(|i| &tokens[i]);
+    let is = |offset: usize, base: &str| at(offset).is_some_and(|t| is_aux(t, base));
+
+    // ませんでした, ました, ません, ましょう, ます
+    let masu = [
+        (
+            &["ます", "ん", "です", "た"][..],
+            ParadigmForm::Nai,
+            "なかった",
+        ),
+        (&["ます", "た"][..], ParadigmForm::Ta, ""),
+        (&["ます", "ん"][..], ParadigmForm::Nai, ""),
+        (&["ます", "う"][..], ParadigmForm::Volitional, ""),
+        (&["ます"][..], ParadigmForm::Dictionary, ""),
+    ];
+    for (pattern, form, past_negative) in masu {
+        let matched = pattern
+            .iter()
+            .rev()
+            .enumerate()
+            .all(|(i, base)| is(i, base));
+        // A lone ます has to be final, not the ませ of いらっしゃいませ
+        if !matched || (pattern.len() == 1 && tokens[end].conjugation_form != ConjugationForm::Basic) {
+            continue;
+        }
+        let stem_index = end.checked_sub(pattern.len())?;
+        let stem = &tokens[stem_index];
+        if stem.base_form == "ござる" {
+            return gozaru_to_plain(tokens, stem_index, pattern.len());
+        }
+        let text = paradigm_of(stem)?.get(form)?.to_string();
+        let text = if past_negative.is_empty() {
+            text
+        } else {
+            format!("{}{past_negative}", text.strip_suffix("ない")?)
+        };
+        return Some((stem_index, text));
+    }
+
+    // でした, でしょう, です
+    if is(1, "です") && is(0, "た") {
+        return Some((end - 1, "だった".to_string()));
+    }
+    if is(1, "です") && is(0, "う") {
+        return Some((end - 1, "だろう".to_string()));
+    }
+    if is(0, "です") {
+        let before = at(1)?;
+        let question = tokens.get(end + 1).is_some_and(|t| t.surface == "か");
+        // 高いです and 行かないです simply drop です, as does 学生ですか
+        let text = if matches!(before.pos.major, PosMajor::Adjective | PosMajor::AuxiliaryVerb)
+            || question
+        {
+            ""
+        } else {
+            "だ"
+        };
+        return Some((end, text.to_string()));
+    }
+    None
+}
+
+/// ございます: ありがとうございます → ありがとう, でございます → だ, other → ある
+fn gozaru_to_plain(
+    tokens: &[AnalyzedToken],
+    stem_index: usize,
+    ending_len: usize,
+) -> Option<(usize, String)> {
+    if ending_len != 1 {
+        return None;
+    }
+    let before = stem_index.checked_sub(1).map(|i| &tokens[i]);
+    match before {
+        Some(t) if t.surface == "で" => Some((stem_index - 1, "だ".to_string())),
+        Some(t) if matches!(t.pos.major, PosMajor::Interjection | PosMajor::Adjective) => {
+            Some((stem_index, String::new()))
+        }
+        _ => Some((stem_index, "ある".to_string())),
+    }
+}
+
+/// Start of the plain predicate ending at `end` and its polite replacement
+fn to_polite(tokens: &[AnalyzedToken], end: usize) -> Option<(usize, String)> {
+    let ending = &tokens[end.saturating_sub(3)..=end];
+    if ending
+        .iter()
+        .any(|t| is_aux(t, "ます") || is_aux(t, "です"))
+    {
+        return None;
+    }
+
+    let last = &tokens[end];
+    let before = end.checked_sub(1).map(|i| &tokens[i]);
+    let add_desu = || Some((end, format!("{}です", last.surface)));
+    let masu_stem = |token: &AnalyzedToken| -> Option<String> {
+        Some(
+            paradigm_of(token)?
+                .get(ParadigmForm::Masu)?
+                .strip_suffix("ます")?
+                .to_string(),
+        )
+    };
+
+    // Past: 食べた, 食べなかった, 学生だった, 高かった
+    if last.pos.major == PosMajor::AuxiliaryVerb && last.conjugation_type == ConjugationType::SpecialTa {
+        let before = before?;
+        if is_aux(before, "だ") {
+            return Some((end - 1, "でした".to_string()));
+        }
+        if before.base_form == "ない"
+            && let Some(verb) = (end - 1).checked_sub(1).map(|i| &tokens[i])
+            && is_verb_like(verb)
+            && verb.conjugation_form.is_irrealis()
+        {
+            return Some((end - 2, format!("{}ませんでした", masu_stem(verb)?)));
+        }
+        if is_verb_like(before) {
+            return Some((end - 1, format!("{}ました", masu_stem(before)?)));
+        }
+        return add_desu();
+    }
+
+    // Negative: 食べない, 高くない, 学生ではない, お金がない
+    let negative = matches!(last.pos.major, PosMajor::AuxiliaryVerb | PosMajor::Adjective);
+    if last.base_form == "ない" && negative {
+        return match before {
+            Some(verb) if is_verb_like(verb) && verb.conjugation_form.is_irrealis() => {
+                Some((end - 1, format!("{}ません", masu_stem(verb)?)))
+            }
+            Some(t) if matches!(t.pos.major, PosMajor::Adjective | PosMajor::AuxiliaryVerb) => {
+                add_desu()
+            }
+            _ => Some((end, "ありません".to_string())),
+        };
+    }
+
+    // Volitional: 食べよう, 行こう, だろう
+    if is_aux(last, "う") || is_aux(last, "よう") {
+        let before = before?;
+        if is_aux(before, "だ") {
+            return Some((end - 1, "でしょう".to_string()));
+        }
+        if is_verb_like(before) {
+            return Some((end - 1, format!("{}ましょう", masu_stem(before)?)));
+        }
+        return None;
+    }
+
+    if last.conjugation_form != ConjugationForm::Basic {
+        // 学生か → 学生ですか
+        let question = tokens.get(end + 1).is_some_and(|t| t.surface == "か");
+        return (last.pos.major == PosMajor::Noun && question)
+            .then(add_desu)
+            .flatten();
+    }
+    if is_aux(last, "だ") {
+        return Some((end, "です".to_string()));
+    }
+    if is_verb_like(last) {
+        return Some((end, paradigm_of(last)?.get(ParadigmForm::Masu)?.to_string()));
+    }
+    if last.pos.major == PosMajor::Adjective || is_aux(last, "たい") {
+        return add_desu();
+    }
+    None
+}
+
+fn is_aux(token: &AnalyzedToken, base: &str) -> bool {
+    token.pos.major == PosMajor::AuxiliaryVerb && token.base_form == base
+}
+
+/// Verbs and the verb-like auxiliaries せる, れる and られる
+fn is_verb_like(token: &AnalyzedToken) -> bool {
+    token.pos.major == PosMajor::Verb
+        || (token.pos.major == PosMajor::AuxiliaryVerb && token.conjugation_type.is_ichidan())
+}
+
+fn paradigm_of(token: &AnalyzedToken) -> Option<Paradigm> {
+    if token.pos.major == PosMajor::AuxiliaryVerb && token.conjugation_type.is_ichidan() {
+        conjugate(&token.base_form, ConjugationClass::Ichidan)
+    } else {
+        token_paradigm(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sentence, token};
+
+    /// Sentence from IPADIC-style `surface/pos/type/form/base` tokens
+    fn parse(spec: &str) -> AnalyzedSentence {
+        sentence(
+            spec.split(' ')
+                .map(|t| {
+                    let f: Vec<&str> = t.split('/').collect();
+                    token(f[0], f[1], f[2], f[3], f[4], "*")
+                })
+                .collect(),
+        )
+    }
+
+    const PERIOD: &str = "。/記号,句点/*/*/。";
+
+    fn plain(spec: &str) -> String {
+        convert_style(&parse(spec), SpeechStyle::Plain).text
+    }
+
+    fn polite(spec: &str) -> String {
+        convert_style(&parse(spec), SpeechStyle::Polite).text
+    }
+
+    #[test]
+    fn masu_to_plain() {
+        let tabemasu = format!(
+            "食べ/動詞,自立/一段/連用形/食べる ます/助動詞/特殊・マス/基本形/ます {PERIOD}"
+        );
+        assert_eq!(plain(&tabemasu), "食べる。");
+        assert_eq!(
+            plain(
+                "行き/動詞,自立/五段・カ行促音便/連用形/行く ませ/助動詞/特殊・マス/未然形/ます \
+                 ん/助動詞/不変化型/基本形/ん でし/助動詞/特殊・デス/連用形/です \
+                 た/助動詞/特殊・タ/基本形/た"
+            ),
+            "行かなかった"
+        );
+        assert_eq!(
+            plain(
+                "書き/動詞,自立/五段・カ行イ音便/連用形/書く まし/助動詞/特殊・マス/連用形/ます \
+                 た/助動詞/特殊・タ/基本形/た"
+            ),
+            "書いた"
+        );
+    }
+
+    #[test]
+    fn desu_to_plain() {
+        assert_eq!(
+            plain("学生/名詞,一般/*/*/学生 です/助動詞/特殊・デス/基本形/です"),
+            "学生だ"
+        );
+        assert_eq!(
+            plain(
+                "学生/名詞,一般/*/*/学生 でし/助動詞/特殊・デス/連用形/です \
+                 た/助動詞/特殊・タ/基本形/た"
+            ),
+            "学生だった"
+        );
+        assert_eq!(
+            plain(
+                "高い/形容詞,自立/形容詞・アウオ段/基本形/高い です/助動詞/特殊・デス/基本形/です"
+            ),
+            "高い"
+        );
+        assert_eq!(
+            plain(
+                "ありがとう/感動詞/*/*/ありがとう ござい/助動詞/五段・ラ行特殊/連用形/ござる \
+                 ます/助動詞/特殊・マス/基本形/ます"
+            ),
+            "ありがとう"
+        );
+    }
+
+    #[test]
+    fn single_kanji_suru_verbs() {
+        assert_eq!(
+            plain(
+                "愛し/動詞,自立/サ変・－スル/連用形/愛する ませ/助動詞/特殊・マス/未然形/ます \
+                 ん/助動詞/不変化型/基本形/ん"
+            ),
+            "愛さない"
+        );
+        assert_eq!(
+            polite("愛さ/動詞,自立/サ変・－スル/未然形/愛する ない/助動詞/特殊・ナイ/基本形/ない"),
+            "愛しません"
+        );
+        assert_eq!(
+            polite("察する/動詞,自立/サ変・－スル/基本形/察する"),
+            "察します"
+        );
+    }
+
+    #[test]
+    fn plain_to_polite() {
+        let taberu = format!("食べる/動詞,自立/一段/基本形/食べる {PERIOD}");
+        assert_eq!(polite(&taberu), "食べます。");
+        assert_eq!(
+            polite(
+                "行か/動詞,自立/五段・カ行促音便/未然形/行く なかっ/助動詞/特殊・ナイ/連用タ接続/ない \
+                 た/助動詞/特殊・タ/基本形/た"
+            ),
+            "行きませんでした"
+        );
+        assert_eq!(
+            polite("学生/名詞,一般/*/*/学生 だ/助動詞/特殊・ダ/基本形/だ"),
+            "学生です"
+        );
+        assert_eq!(
+            polite("高い/形容詞,自立/形容詞・アウオ段/基本形/高い"),
+            "高いです"
+        );
+        assert_eq!(
+            polite(
+                "行こ/動詞,自立/五段・カ行促音便/未然ウ接続/行く う/助動詞/不変化型/基本形/う \
+                 ね/助詞,終助詞/*/*/ね"
+            ),
+            "行きましょうね"
+        );
+    }
+
+    #[test]
+    fn clauses_and_converted_styles_are_kept() {
+        let polite_sentence =
+            parse("食べ/動詞,自立/一段/連用形/食べる ます/助動詞/特殊・マス/基本形/ます");
+        assert!(
+            convert_style(&polite_sentence, SpeechStyle::Polite)
+                .changes
+                .is_empty()
+        );
+
+        // Only the predicate before 。 is rewritten, not 食べ in the て clause
+        let conversion = convert_style(
+            &parse(&format!(
+                "食べ/動詞,自立/一段/連用形/食べる て/助詞,接続助詞/*/*/て \
+                 寝る/動詞,自立/一段/基本形/寝る {PERIOD}"
+            )),
+            SpeechStyle::Polite,
+        );
+        assert_eq!(conversion.text, "食べて寝ます。");
+        assert_eq!(
+            conversion.changes,
+            vec![StyleChange {
+                start: 2,
+                end: 3,
+                original: "寝る".to_string(),
+                converted: "寝ます".to_string(),
+            }]
+        );
+    }
+}
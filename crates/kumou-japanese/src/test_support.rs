@@ -0,0 +1,68 @@
+//! Hand-built tokens for unit tests, so they run without a dictionary
+
+use crate::tokenizer::{AnalyzedSentence, AnalyzedToken, PartOfSpeech};
+
+/// Token with IPADIC-style fields; `pos` is "major,sub1", optionally
+/// followed by ",sub2" and ",sub3"
+pub(crate) fn token(
+    surface: &str,
+    pos: &str,
+    conjugation_type: &str,
+    conjugation_form: &str,
+    base_form: &str,
+    reading: &str,
+) -> AnalyzedToken {
+    let mut levels = pos.split(',');
+    let mut level = || levels.next().unwrap_or("*");
+    let (major, sub1, sub2, sub3) = (level(), level(), level(), level());
+    AnalyzedToken {
+        surface: surface.to_string(),
+        pos: PartOfSpeech {
+            major: major.into(),
+            sub1: sub1.into(),
+            sub2: sub2.into(),
+            sub3: sub3.into(),
+        },
+        conjugation_type: conjugation_type.into(),
+        conjugation_form: conjugation_form.into(),
+        base_form: base_form.to_string(),
+        reading: reading.to_string(),
+        pronunciation: reading.to_string(),
+        lexeme: None,
+        accent: None,
+        jlpt: None,
+        glosses: Vec::new(),
+        byte_start: 0,
+        byte_end: 0,
+        char_start: 0,
+        char_end: 0,
+    }
+}
+
+/// Sentence of `tokens` laid end to end, with their offsets filled in
+pub(crate) fn sentence(tokens: Vec<AnalyzedToken>) -> AnalyzedSentence {
+    let mut text = String::new();
+    let mut chars = 0;
+    let tokens = tokens
+        .into_iter()
+        .map(|token| {
+            let char_end = chars + token.surface.chars().count();
+            let located = AnalyzedToken {
+                byte_start: text.len(),
+                byte_end: text.len() + token.surface.len(),
+                char_start: chars,
+                char_end,
+                ..token
+            };
+            text.push_str(&located.surface);
+            chars = char_end;
+            located
+        })
+        .collect();
+    AnalyzedSentence {
+        text,
+        tokens,
+        dependencies: Default::default(),
+        normalized: None,
+    }
+}
@@ -1,27 +1,42 @@
 #[cfg(feature = "tokenizer")]
 use lindera::dictionary::{load_embedded_dictionary, DictionaryKind};
 #[cfg(feature = "tokenizer")]
-use lindera::mode::Mode;
+use lindera::mode::{Mode, Penalty};
 #[cfg(feature = "tokenizer")]
 use lindera::segmenter::Segmenter;
 #[cfg(feature = "tokenizer")]
 use lindera::tokenizer::Tokenizer;
+use std::ops::Range;
+
 use serde::{Deserialize, Serialize};
 
+use crate::accent::PitchAccent;
+use crate::jlpt::JlptLevel;
+#[cfg(feature = "tokenizer")]
+use crate::config::{AnalyzerConfig, DictionaryBackend, SegmentationMode};
+use crate::dependency::DependencyTree;
+#[cfg(feature = "tokenizer")]
+use crate::dependency::parse_dependencies;
+use crate::normalize::NormalizedText;
+use crate::pos::{ConjugationForm, ConjugationType, PosMajor, PosSub};
+#[cfg(feature = "tokenizer")]
+use crate::normalize::Normalization;
 #[cfg(feature = "tokenizer")]
 use crate::error::AnalysisError;
+#[cfg(feature = "tokenizer")]
+use crate::user_dictionary::{UserDictionary, build_lindera_user_dictionary};
 
 /// Part-of-speech information from IPADIC
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PartOfSpeech {
     /// Major POS category (品詞): 名詞, 動詞, 助詞, etc.
-    pub major: String,
+    pub major: PosMajor,
     /// POS subcategory 1 (品詞細分類1)
-    pub sub1: String,
+    pub sub1: PosSub,
     /// POS subcategory 2 (品詞細分類2)
-    pub sub2: String,
+    pub sub2: PosSub,
     /// POS subcategory 3 (品詞細分類3)
-    pub sub3: String,
+    pub sub3: PosSub,
 }
 
 /// A single analyzed token with grammar details
@@ -32,15 +47,64 @@ pub struct AnalyzedToken {
     /// Part-of-speech information
     pub pos: PartOfSpeech,
     /// Conjugation type (活用型), e.g. 一段, 五段
-    pub conjugation_type: String,
+    pub conjugation_type: ConjugationType,
     /// Conjugation form (活用形), e.g. 基本形, 連用形
-    pub conjugation_form: String,
+    pub conjugation_form: ConjugationForm,
     /// Base/dictionary form (原形)
     pub base_form: String,
     /// Katakana reading (読み)
     pub reading: String,
     /// Pronunciation (発音)
     pub pronunciation: String,
+    /// Lexeme details only UniDic provides
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lexeme: Option<Lexeme>,
+    /// Pitch accent from an [`crate::AccentDictionary`], when annotated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent: Option<PitchAccent>,
+    /// JLPT level of the dictionary form from [`crate::JlptLists`], when annotated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jlpt: Option<JlptLevel>,
+    /// English glosses from [`crate::Jmdict`], one per sense, when annotated
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub glosses: Vec<String>,
+    /// Byte offset of the token in the original sentence text
+    #[serde(default)]
+    pub byte_start: usize,
+    /// Byte offset one past the token in the original sentence text
+    #[serde(default)]
+    pub byte_end: usize,
+    /// Char offset of the token in the original sentence text
+    #[serde(default)]
+    pub char_start: usize,
+    /// Char offset one past the token in the original sentence text
+    #[serde(default)]
+    pub char_end: usize,
+}
+
+impl AnalyzedToken {
+    /// Byte range of the token in the original text, for slicing it
+    pub fn byte_range(&self) -> Range<usize> {
+        self.byte_start..self.byte_end
+    }
+
+    /// Char range of the token in the original text
+    pub fn char_range(&self) -> Range<usize> {
+        self.char_start..self.char_end
+    }
+}
+
+/// Lexeme (語彙素) information from UniDic
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Lexeme {
+    /// Lemma (語彙素), e.g. 食べる for 食べ
+    pub lemma: String,
+    /// Katakana reading of the lemma (語彙素読み)
+    pub lemma_reading: String,
+    /// Pronunciation of the base form (発音形基本形)
+    pub base_pronunciation: String,
+    /// Word origin (語種): 和, 漢, 外, 混, etc.
+    pub word_type: String,
 }
 
 /// Result of analyzing a sentence
@@ -50,101 +114,265 @@ pub struct AnalyzedSentence {
     pub text: String,
     /// Analyzed tokens
     pub tokens: Vec<AnalyzedToken>,
+    /// Bunsetsu dependencies between the tokens
+    #[serde(default)]
+    pub dependencies: DependencyTree,
+    /// Text the tokens were read from, when normalization changed it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalized: Option<NormalizedText>,
+}
+
+impl AnalyzedSentence {
+    /// Char range in `text` of each token
+    ///
+    /// The same as [`AnalyzedToken::char_range`] of every token, in order.
+    pub fn source_spans(&self) -> Vec<Range<usize>> {
+        self.tokens.iter().map(AnalyzedToken::char_range).collect()
+    }
 }
 
 /// Create a lindera tokenizer with IPADIC dictionary
 #[cfg(feature = "tokenizer")]
 pub fn create_tokenizer() -> Result<Tokenizer, AnalysisError> {
-    let dictionary = load_embedded_dictionary(DictionaryKind::IPADIC)
+    create_tokenizer_with_config(&AnalyzerConfig::default())
+}
+
+/// Create a lindera tokenizer with the dictionary selected in `config`
+///
+/// The user dictionary, if configured, is read and validated here.
+#[cfg(feature = "tokenizer")]
+pub fn create_tokenizer_with_config(config: &AnalyzerConfig) -> Result<Tokenizer, AnalysisError> {
+    let dictionary = load_embedded_dictionary(dictionary_kind(config.dictionary)?)
         .map_err(|e| AnalysisError::TokenizerInit(e.to_string()))?;
-    let segmenter = Segmenter::new(Mode::Normal, dictionary, None);
+
+    let user_dictionary = match &config.user_dictionary {
+        Some(path) => {
+            let user_dictionary = UserDictionary::load(path)?;
+            if user_dictionary.entries.is_empty() {
+                None
+            } else {
+                Some(build_lindera_user_dictionary(
+                    &user_dictionary,
+                    &dictionary.metadata,
+                )?)
+            }
+        }
+        None => None,
+    };
+
+    let segmenter = Segmenter::new(lindera_mode(config.mode), dictionary, user_dictionary);
     Ok(Tokenizer::new(segmenter))
 }
 
+/// Create a tokenizer sharing `tokenizer`'s dictionaries but segmenting in `mode`
+#[cfg(feature = "tokenizer")]
+pub fn create_tokenizer_with_mode(tokenizer: &Tokenizer, mode: SegmentationMode) -> Tokenizer {
+    let segmenter = Segmenter::new(
+        lindera_mode(mode),
+        tokenizer.segmenter.dictionary.clone(),
+        tokenizer.segmenter.user_dictionary.clone(),
+    );
+    Tokenizer::new(segmenter)
+}
+
+#[cfg(feature = "tokenizer")]
+fn lindera_mode(mode: SegmentationMode) -> Mode {
+    match mode {
+        SegmentationMode::Normal => Mode::Normal,
+        SegmentationMode::Decompose => Mode::Decompose(Penalty::default()),
+    }
+}
+
+#[cfg(feature = "tokenizer")]
+fn dictionary_kind(backend: DictionaryBackend) -> Result<DictionaryKind, AnalysisError> {
+    match backend {
+        DictionaryBackend::Ipadic => Ok(DictionaryKind::IPADIC),
+        #[cfg(feature = "embed-ipadic-neologd")]
+        DictionaryBackend::IpadicNeologd => Ok(DictionaryKind::IPADICNEologd),
+        #[cfg(feature = "embed-unidic")]
+        DictionaryBackend::UniDic => Ok(DictionaryKind::UniDic),
+        #[allow(unreachable_patterns)]
+        other => Err(AnalysisError::DictionaryUnavailable(
+            other.as_str().to_string(),
+            other.feature().to_string(),
+        )),
+    }
+}
+
 /// Analyze a Japanese sentence into tokens with grammar details
+///
+/// The detail layout is picked from the tokenizer's dictionary, so any
+/// tokenizer built by [`create_tokenizer_with_config`] can be passed in.
+/// The text is tokenized as given; see [`analyze_normalized_sentence`].
 #[cfg(feature = "tokenizer")]
 pub fn analyze_sentence(
     tokenizer: &Tokenizer,
     text: &str,
 ) -> Result<AnalyzedSentence, AnalysisError> {
+    analyze_normalized_sentence(tokenizer, text, Normalization::None)
+}
+
+/// Analyze a Japanese sentence after applying `normalization` to it
+///
+/// Token surfaces are taken from the normalized text, while the sentence
+/// keeps the original; token offsets and [`AnalyzedSentence::source_spans`]
+/// point into the original. A token that covers part of what one original
+/// character expanded into, like 平 of ㍻ → 平成, spans that whole character.
+#[cfg(feature = "tokenizer")]
+pub fn analyze_normalized_sentence(
+    tokenizer: &Tokenizer,
+    text: &str,
+    normalization: Normalization,
+) -> Result<AnalyzedSentence, AnalysisError> {
+    let is_unidic =
+        tokenizer.segmenter.dictionary.metadata.name == DictionaryBackend::UniDic.as_str();
+
+    let normalized = NormalizedText::new(text, normalization);
     let mut tokens_result = tokenizer
-        .tokenize(text)
+        .tokenize(&normalized.text)
         .map_err(|e| AnalysisError::Tokenization(e.to_string()))?;
 
+    let original_bytes: Vec<usize> = text
+        .char_indices()
+        .map(|(index, _)| index)
+        .chain([text.len()])
+        .collect();
+    // Where the previous token ended in the normalized text, in bytes and chars
+    let (mut byte_cursor, mut char_cursor) = (0, 0);
+
     let mut analyzed_tokens = Vec::new();
 
     for token in tokens_result.iter_mut() {
+        let normalized_text = &normalized.text;
+        let char_start =
+            char_cursor + normalized_text[byte_cursor..token.byte_start].chars().count();
+        let char_end =
+            char_start + normalized_text[token.byte_start..token.byte_end].chars().count();
+        (byte_cursor, char_cursor) = (token.byte_end, char_end);
+        let chars = normalized.original_span(char_start..char_end);
+
         let details: Vec<String> = token
             .details()
             .iter()
             .map(|s| s.to_string())
             .collect();
 
-        // IPADIC returns 9 fields:
-        // [0] POS, [1] sub1, [2] sub2, [3] sub3,
-        // [4] conjugation_type, [5] conjugation_form,
-        // [6] base_form, [7] reading, [8] pronunciation
-        let get = |i: usize| -> String {
-            details
-                .get(i)
-                .cloned()
-                .unwrap_or_else(|| "*".to_string())
+        let analyzed = if is_unidic {
+            unidic_token(token.surface.to_string(), &details)
+        } else {
+            ipadic_token(token.surface.to_string(), &details)
         };
-
         analyzed_tokens.push(AnalyzedToken {
-            surface: token.surface.to_string(),
-            pos: PartOfSpeech {
-                major: get(0),
-                sub1: get(1),
-                sub2: get(2),
-                sub3: get(3),
-            },
-            conjugation_type: get(4),
-            conjugation_form: get(5),
-            base_form: get(6),
-            reading: get(7),
-            pronunciation: get(8),
+            byte_start: original_bytes[chars.start],
+            byte_end: original_bytes[chars.end],
+            char_start: chars.start,
+            char_end: chars.end,
+            ..analyzed
         });
     }
 
     Ok(AnalyzedSentence {
         text: text.to_string(),
+        dependencies: parse_dependencies(&analyzed_tokens),
         tokens: analyzed_tokens,
+        normalized: (!normalized.is_unchanged(text)).then_some(normalized),
     })
 }
 
-/// Get a CSS class name for a POS major category (for UI coloring)
-pub fn pos_css_class(major: &str) -> &str {
-    match major {
-        "名詞" => "pos-noun",
-        "動詞" => "pos-verb",
-        "形容詞" => "pos-adjective",
-        "副詞" => "pos-adverb",
-        "助詞" => "pos-particle",
-        "助動詞" => "pos-aux-verb",
-        "接続詞" => "pos-conjunction",
-        "感動詞" => "pos-interjection",
-        "連体詞" => "pos-adnominal",
-        "記号" => "pos-symbol",
-        _ => "pos-other",
+#[cfg(feature = "tokenizer")]
+fn detail(details: &[String], i: usize) -> String {
+    details
+        .get(i)
+        .cloned()
+        .unwrap_or_else(|| "*".to_string())
+}
+
+/// Map IPADIC (and IPADIC-NEologd) details into a token
+#[cfg(feature = "tokenizer")]
+fn ipadic_token(surface: String, details: &[String]) -> AnalyzedToken {
+    // IPADIC returns 9 fields:
+    // [0] POS, [1] sub1, [2] sub2, [3] sub3,
+    // [4] conjugation_type, [5] conjugation_form,
+    // [6] base_form, [7] reading, [8] pronunciation
+    let get = |i: usize| detail(details, i);
+
+    AnalyzedToken {
+        surface,
+        pos: PartOfSpeech {
+            major: get(0).into(),
+            sub1: get(1).into(),
+            sub2: get(2).into(),
+            sub3: get(3).into(),
+        },
+        conjugation_type: get(4).into(),
+        conjugation_form: get(5).into(),
+        base_form: get(6),
+        reading: get(7),
+        pronunciation: get(8),
+        lexeme: None,
+        accent: None,
+        jlpt: None,
+        glosses: Vec::new(),
+        byte_start: 0,
+        byte_end: 0,
+        char_start: 0,
+        char_end: 0,
     }
 }
 
-/// Get English translation for a POS major category
-pub fn pos_english(major: &str) -> &str {
-    match major {
-        "名詞" => "Noun",
-        "動詞" => "Verb",
-        "形容詞" => "i-Adjective",
-        "形容動詞" => "na-Adjective",
-        "副詞" => "Adverb",
-        "助詞" => "Particle",
-        "助動詞" => "Aux. Verb",
-        "接続詞" => "Conjunction",
-        "感動詞" => "Interjection",
-        "連体詞" => "Adnominal",
-        "記号" => "Symbol",
-        "フィラー" => "Filler",
-        _ => "Other",
+/// Map UniDic details into a token
+#[cfg(feature = "tokenizer")]
+fn unidic_token(surface: String, details: &[String]) -> AnalyzedToken {
+    // UniDic returns 17 fields:
+    // [0] POS, [1] sub1, [2] sub2, [3] sub3,
+    // [4] conjugation_type, [5] conjugation_form,
+    // [6] lemma reading, [7] lemma,
+    // [8] orthographic surface, [9] phonological surface,
+    // [10] orthographic base, [11] phonological base,
+    // [12] word type, [13..16] mutation types/forms
+    //
+    // Unknown words only carry the first few fields.
+    let get = |i: usize| detail(details, i);
+
+    let lexeme = (details.len() > 12).then(|| Lexeme {
+        lemma: get(7),
+        lemma_reading: get(6),
+        base_pronunciation: get(11),
+        word_type: get(12),
+    });
+
+    AnalyzedToken {
+        surface,
+        pos: PartOfSpeech {
+            major: get(0).into(),
+            sub1: get(1).into(),
+            sub2: get(2).into(),
+            sub3: get(3).into(),
+        },
+        conjugation_type: get(4).into(),
+        conjugation_form: get(5).into(),
+        base_form: get(10),
+        // UniDic has no kana form of the surface, the phonological form is
+        // the closest match.
+        reading: get(9),
+        pronunciation: get(9),
+        lexeme,
+        accent: None,
+        jlpt: None,
+        glosses: Vec::new(),
+        byte_start: 0,
+        byte_end: 0,
+        char_start: 0,
+        char_end: 0,
     }
 }
+
+/// Get a CSS class name for a POS major category (for UI coloring)
+pub fn pos_css_class(major: &PosMajor) -> &'static str {
+    major.css_class()
+}
+
+/// Get English translation for a POS major category
+pub fn pos_english(major: &PosMajor) -> &'static str {
+    major.english()
+}
@@ -0,0 +1,367 @@
+use serde::{Deserialize, Serialize};
+
+use crate::kana::katakana_to_hiragana;
+use crate::tokenizer::AnalyzedToken;
+
+/// Romanization system
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum RomajiSystem {
+    /// Modified Hepburn: shi, chi, tsu, fu, ja; long vowels as ā ī ū ē ō
+    #[default]
+    Hepburn,
+    /// Kunrei-shiki: si, ti, tu, hu, zya; long vowels as â î û ê ô
+    Kunrei,
+}
+
+/// How token readings are shown in the UI
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadingDisplay {
+    /// Katakana as given by the dictionary
+    #[default]
+    Katakana,
+    Hiragana,
+    Hepburn,
+    Kunrei,
+}
+
+impl ReadingDisplay {
+    pub const ALL: [ReadingDisplay; 4] = [
+        ReadingDisplay::Katakana,
+        ReadingDisplay::Hiragana,
+        ReadingDisplay::Hepburn,
+        ReadingDisplay::Kunrei,
+    ];
+
+    /// Identifier used in configuration and form values, e.g. `hepburn`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReadingDisplay::Katakana => "katakana",
+            ReadingDisplay::Hiragana => "hiragana",
+            ReadingDisplay::Hepburn => "hepburn",
+            ReadingDisplay::Kunrei => "kunrei",
+        }
+    }
+
+    /// Label shown to users
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReadingDisplay::Katakana => "カタカナ",
+            ReadingDisplay::Hiragana => "ひらがな",
+            ReadingDisplay::Hepburn => "Hepburn",
+            ReadingDisplay::Kunrei => "Kunrei",
+        }
+    }
+}
+
+/// Format a token's reading for display
+///
+/// Romaji is built from the pronunciation (発音) when available, so long
+/// vowels and particles come out as spoken: 東京 → tōkyō, は → wa.
+pub fn display_reading(token: &AnalyzedToken, display: ReadingDisplay) -> String {
+    if token.reading == "*" {
+        return String::new();
+    }
+    let spoken = if token.pronunciation == "*" {
+        &token.reading
+    } else {
+        &token.pronunciation
+    };
+    match display {
+        ReadingDisplay::Katakana => token.reading.clone(),
+        ReadingDisplay::Hiragana => katakana_to_hiragana(&token.reading),
+        ReadingDisplay::Hepburn => to_romaji(spoken, RomajiSystem::Hepburn),
+        ReadingDisplay::Kunrei => to_romaji(spoken, RomajiSystem::Kunrei),
+    }
+}
+
+/// Romanize kana text
+///
+/// Handles sokuon (っ → doubled consonant, tch in Hepburn, or an apostrophe
+/// when no consonant follows: あっ → a'), syllabic ん (n' before vowels and
+/// y), yōon (きゃ → kya) and small vowels in loanwords (ファ → fa, ティ → ti).
+/// The long vowel mark ー lengthens the previous vowel; kana vowel sequences
+/// such as おう are kept as written since they are not always long.
+/// Characters that are not kana pass through.
+pub fn to_romaji(kana: &str, system: RomajiSystem) -> String {
+    let chars: Vec<char> = katakana_to_hiragana(kana).chars().collect();
+    let mut out = String::new();
+    let mut sokuon = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == 'っ' {
+            end_sokuon(&mut out, &mut sokuon);
+            sokuon = true;
+            i += 1;
+            continue;
+        }
+        if c == 'ー' {
+            end_sokuon(&mut out, &mut sokuon);
+            lengthen_last_vowel(&mut out, system);
+            i += 1;
+            continue;
+        }
+
+        let (syllable, used) = match syllable(c, chars.get(i + 1).copied(), system) {
+            Some(found) => found,
+            None => {
+                end_sokuon(&mut out, &mut sokuon);
+                out.push(c);
+                i += 1;
+                continue;
+            }
+        };
+
+        if c == 'ん' {
+            end_sokuon(&mut out, &mut sokuon);
+            let next = chars
+                .get(i + 1)
+                .and_then(|&n| syllable_romaji(n, system))
+                .and_then(|r| r.chars().next());
+            out.push('n');
+            if matches!(next, Some('a' | 'i' | 'u' | 'e' | 'o' | 'y')) {
+                out.push('\'');
+            }
+        } else {
+            if sokuon {
+                if system == RomajiSystem::Hepburn && syllable.starts_with("ch") {
+                    out.push('t');
+                } else if let Some(first) = syllable.chars().next().filter(|c| !is_vowel(*c)) {
+                    out.push(first);
+                } else {
+                    out.push('\'');
+                }
+            }
+            out.push_str(&syllable);
+        }
+
+        sokuon = false;
+        i += used;
+    }
+    end_sokuon(&mut out, &mut sokuon);
+
+    out
+}
+
+/// Write a pending っ that no consonant doubles as an apostrophe
+fn end_sokuon(out: &mut String, sokuon: &mut bool) {
+    if std::mem::take(sokuon) {
+        out.push('\'');
+    }
+}
+
+/// Romaji for the syllable starting at `c`, and how many kana it used
+fn syllable(c: char, next: Option<char>, system: RomajiSystem) -> Option<(String, usize)> {
+    let base = syllable_romaji(c, system)?;
+
+    let Some(small) = next.and_then(small_vowel) else {
+        return Some((base.to_string(), 1));
+    };
+    if c == 'ん' || (base.len() < 2 && c != 'う') {
+        return Some((base.to_string(), 1));
+    }
+
+    let stem = &base[..base.len() - 1];
+    let combined = if base.ends_with('i') && !matches!(c, 'い' | 'ぃ') {
+        // Yōon and i-row + small vowel: きゃ → kya, しゃ → sha, チェ → che
+        let vowel = small.trim_start_matches('y');
+        match (system, stem) {
+            (RomajiSystem::Hepburn, "sh" | "ch" | "j") => format!("{stem}{vowel}"),
+            _ => format!("{stem}y{vowel}"),
+        }
+    } else if small.starts_with('y') {
+        return Some((base.to_string(), 1));
+    } else {
+        // Loanword combinations: ファ → fa, ティ → ti, ウィ → wi
+        let stem = match c {
+            'う' => "w",
+            'ふ' => "f",
+            _ => stem,
+        };
+        format!("{stem}{small}")
+    };
+    Some((combined, 2))
+}
+
+fn small_vowel(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ぁ' => "a",
+        'ぃ' => "i",
+        'ぅ' => "u",
+        'ぇ' => "e",
+        'ぉ' => "o",
+        'ゃ' => "ya",
+        'ゅ' => "yu",
+        'ょ' => "yo",
+        _ => return None,
+    })
+}
+
+fn syllable_romaji(c: char, system: RomajiSystem) -> Option<&'static str> {
+    let hepburn = system == RomajiSystem::Hepburn;
+    Some(match c {
+        'あ' | 'ぁ' => "a",
+        'い' | 'ぃ' | 'ゐ' => "i",
+        'う' | 'ぅ' => "u",
+        'え' | 'ぇ' | 'ゑ' => "e",
+        'お' | 'ぉ' | 'を' => "o",
+        'か' | 'ゕ' => "ka",
+        'き' => "ki",
+        'く' => "ku",
+        'け' | 'ゖ' => "ke",
+        'こ' => "ko",
+        'が' => "ga",
+        'ぎ' => "gi",
+        'ぐ' => "gu",
+        'げ' => "ge",
+        'ご' => "go",
+        'さ' => "sa",
+        'し' => if hepburn { "shi" } else { "si" },
+        'す' => "su",
+        'せ' => "se",
+        'そ' => "so",
+        'ざ' => "za",
+        'じ' | 'ぢ' => if hepburn { "ji" } else { "zi" },
+        'ず' | 'づ' => "zu",
+        'ぜ' => "ze",
+        'ぞ' => "zo",
+        'た' => "ta",
+        'ち' => if hepburn { "chi" } else { "ti" },
+        'つ' => if hepburn { "tsu" } else { "tu" },
+        'て' => "te",
+        'と' => "to",
+        'だ' => "da",
+        'で' => "de",
+        'ど' => "do",
+        'な' => "na",
+        'に' => "ni",
+        'ぬ' => "nu",
+        'ね' => "ne",
+        'の' => "no",
+        'は' => "ha",
+        'ひ' => "hi",
+        'ふ' => if hepburn { "fu" } else { "hu" },
+        'へ' => "he",
+        'ほ' => "ho",
+        'ば' => "ba",
+        'び' => "bi",
+        'ぶ' => "bu",
+        'べ' => "be",
+        'ぼ' => "bo",
+        'ぱ' => "pa",
+        'ぴ' => "pi",
+        'ぷ' => "pu",
+        'ぺ' => "pe",
+        'ぽ' => "po",
+        'ま' => "ma",
+        'み' => "mi",
+        'む' => "mu",
+        'め' => "me",
+        'も' => "mo",
+        'や' | 'ゃ' => "ya",
+        'ゆ' | 'ゅ' => "yu",
+        'よ' | 'ょ' => "yo",
+        'ら' => "ra",
+        'り' => "ri",
+        'る' => "ru",
+        'れ' => "re",
+        'ろ' => "ro",
+        'わ' | 'ゎ' => "wa",
+        'ん' => "n",
+        'ゔ' => "vu",
+        _ => return None,
+    })
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'i' | 'u' | 'e' | 'o')
+}
+
+fn lengthen_last_vowel(out: &mut String, system: RomajiSystem) {
+    let Some(last) = out.pop() else {
+        return;
+    };
+    let long = match (system, last) {
+        (RomajiSystem::Hepburn, 'a') => 'ā',
+        (RomajiSystem::Hepburn, 'i') => 'ī',
+        (RomajiSystem::Hepburn, 'u') => 'ū',
+        (RomajiSystem::Hepburn, 'e') => 'ē',
+        (RomajiSystem::Hepburn, 'o') => 'ō',
+        (RomajiSystem::Kunrei, 'a') => 'â',
+        (RomajiSystem::Kunrei, 'i') => 'î',
+        (RomajiSystem::Kunrei, 'u') => 'û',
+        (RomajiSystem::Kunrei, 'e') => 'ê',
+        (RomajiSystem::Kunrei, 'o') => 'ô',
+        _ => {
+            out.push(last);
+            return;
+        }
+    };
+    out.push(long);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hepburn() {
+        for (kana, romaji) in [
+            ("しんぶん", "shinbun"),
+            ("ちゃ", "cha"),
+            ("きょう", "kyou"),
+            ("トーキョー", "tōkyō"),
+            ("きっぷ", "kippu"),
+            ("まっちゃ", "matcha"),
+            ("ざっし", "zasshi"),
+            ("きんえん", "kin'en"),
+            ("ほんや", "hon'ya"),
+            ("ファイル", "fairu"),
+            ("パーティー", "pātī"),
+            ("ウィキ", "wiki"),
+            ("チェック", "chekku"),
+            ("ジャズ", "jazu"),
+        ] {
+            assert_eq!(to_romaji(kana, RomajiSystem::Hepburn), romaji, "{kana}");
+        }
+    }
+
+    #[test]
+    fn kunrei() {
+        for (kana, romaji) in [
+            ("しんぶん", "sinbun"),
+            ("ちゃ", "tya"),
+            ("じゃ", "zya"),
+            ("つくえ", "tukue"),
+            ("ふじ", "huzi"),
+            ("まっちゃ", "mattya"),
+            ("トーキョー", "tôkyô"),
+        ] {
+            assert_eq!(to_romaji(kana, RomajiSystem::Kunrei), romaji, "{kana}");
+        }
+    }
+
+    #[test]
+    fn sokuon_without_consonant() {
+        for (kana, romaji) in [
+            ("まっ", "ma'"),
+            ("あっ！", "a'！"),
+            ("えっ?", "e'?"),
+            ("あっあ", "a'a"),
+            ("っっか", "'kka"),
+            ("ウッン", "u'n"),
+        ] {
+            assert_eq!(to_romaji(kana, RomajiSystem::Hepburn), romaji, "{kana}");
+        }
+    }
+
+    #[test]
+    fn non_kana_passes_through() {
+        assert_eq!(to_romaji("東京タワー", RomajiSystem::Hepburn), "東京tawā");
+        assert_eq!(to_romaji("", RomajiSystem::Hepburn), "");
+    }
+}
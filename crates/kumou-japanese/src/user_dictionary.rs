@@ -0,0 +1,301 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AnalysisError;
+use crate::kana::is_katakana;
+use crate::pos::PosMajor;
+use crate::tokenizer::PartOfSpeech;
+
+/// A custom vocabulary entry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UserDictionaryEntry {
+    /// Surface form to match in text
+    pub surface: String,
+    /// Part-of-speech assigned to matches
+    pub pos: PartOfSpeech,
+    /// Katakana reading (読み)
+    pub reading: String,
+    /// Base/dictionary form (原形)
+    pub base_form: String,
+}
+
+/// Custom vocabulary and corrections applied on top of the system dictionary
+///
+/// The CSV format has one entry per line with four columns:
+///
+/// ```text
+/// # surface,POS,reading,base form
+/// 東京スカイツリー,名詞-固有名詞-一般,トウキョウスカイツリー,東京スカイツリー
+/// ググる,動詞-自立,ググル,
+/// ```
+///
+/// The POS column lists up to four levels separated by `-`. An empty base
+/// form defaults to the surface. Blank lines and lines starting with `#` are
+/// ignored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UserDictionary {
+    pub entries: Vec<UserDictionaryEntry>,
+}
+
+impl UserDictionary {
+    /// Parse and validate a user dictionary from CSV text
+    pub fn parse(csv: &str) -> Result<Self, AnalysisError> {
+        let mut entries: Vec<UserDictionaryEntry> = Vec::new();
+
+        for (index, raw) in csv.lines().enumerate() {
+            let line = index + 1;
+            let raw = raw.trim();
+            if raw.is_empty() || raw.starts_with('#') {
+                continue;
+            }
+
+            let columns: Vec<&str> = raw.split(',').map(str::trim).collect();
+            let [surface, pos, reading, base_form] = columns[..] else {
+                return Err(AnalysisError::UserDictionaryColumns {
+                    line,
+                    found: columns.len(),
+                });
+            };
+
+            if surface.is_empty() {
+                return Err(AnalysisError::UserDictionaryEmptySurface { line });
+            }
+            if entries.iter().any(|e| e.surface == surface) {
+                return Err(AnalysisError::UserDictionaryDuplicate {
+                    line,
+                    surface: surface.to_string(),
+                });
+            }
+
+            let levels: Vec<&str> = pos.split('-').collect();
+            // Only the POS major categories of IPADIC and UniDic are accepted
+            let known = PosMajor::ALL.contains(&PosMajor::from(levels[0]));
+            if levels.len() > 4 || !known {
+                return Err(AnalysisError::UserDictionaryPos {
+                    line,
+                    pos: pos.to_string(),
+                });
+            }
+            let level = |i: usize| {
+                levels
+                    .get(i)
+                    .filter(|s| !s.is_empty())
+                    .unwrap_or(&"*")
+                    .to_string()
+            };
+
+            if reading.is_empty() || !reading.chars().all(is_katakana) {
+                return Err(AnalysisError::UserDictionaryReading {
+                    line,
+                    reading: reading.to_string(),
+                });
+            }
+
+            entries.push(UserDictionaryEntry {
+                surface: surface.to_string(),
+                pos: PartOfSpeech {
+                    major: level(0).into(),
+                    sub1: level(1).into(),
+                    sub2: level(2).into(),
+                    sub3: level(3).into(),
+                },
+                reading: reading.to_string(),
+                base_form: if base_form.is_empty() { surface } else { base_form }.to_string(),
+            });
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Read and validate a user dictionary CSV file
+    pub fn load(path: &Path) -> Result<Self, AnalysisError> {
+        let csv = std::fs::read_to_string(path)
+            .map_err(|e| AnalysisError::UserDictionaryRead(format!("{}: {e}", path.display())))?;
+        Self::parse(&csv)
+    }
+}
+
+/// Build a lindera user dictionary laid out for the system dictionary `metadata`
+///
+/// Entries use the dictionary's default context IDs and word cost, like
+/// lindera's own simple user dictionary format.
+#[cfg(feature = "tokenizer")]
+pub(crate) fn build_lindera_user_dictionary(
+    dictionary: &UserDictionary,
+    metadata: &lindera::dictionary::Metadata,
+) -> Result<lindera::dictionary::UserDictionary, AnalysisError> {
+    let mut csv = String::new();
+    for entry in &dictionary.entries {
+        let row: Vec<String> = metadata
+            .dictionary_schema
+            .get_all_fields()
+            .iter()
+            .map(|field| match field.as_str() {
+                "surface" | "orthographic_surface_form" => entry.surface.clone(),
+                "left_context_id" => metadata.default_left_context_id.to_string(),
+                "right_context_id" => metadata.default_right_context_id.to_string(),
+                "cost" => metadata.default_word_cost.to_string(),
+                "part_of_speech" => entry.pos.major.to_string(),
+                "part_of_speech_subcategory_1" => entry.pos.sub1.to_string(),
+                "part_of_speech_subcategory_2" => entry.pos.sub2.to_string(),
+                "part_of_speech_subcategory_3" => entry.pos.sub3.to_string(),
+                "base_form" | "orthographic_base_form" | "lexeme" => entry.base_form.clone(),
+                "reading" | "pronunciation" | "phonological_surface_form" => {
+                    entry.reading.clone()
+                }
+                _ => metadata.default_field_value.clone(),
+            })
+            .collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    // lindera only builds user dictionaries from files.
+    let file =
+        TempCsv::create(&csv).map_err(|e| AnalysisError::UserDictionaryRead(e.to_string()))?;
+    lindera::dictionary::load_user_dictionary_from_csv(metadata, &file.path)
+        .map_err(|e| AnalysisError::TokenizerInit(e.to_string()))
+}
+
+/// A CSV file in the temp dir that this process created, removed on drop
+///
+/// The file is created with `create_new`, so an existing file or symlink
+/// under the same name is never written through, and on Unix only the
+/// owner can read it.
+#[cfg(feature = "tokenizer")]
+struct TempCsv {
+    path: std::path::PathBuf,
+}
+
+#[cfg(feature = "tokenizer")]
+impl TempCsv {
+    const ATTEMPTS: usize = 16;
+
+    fn create(contents: &str) -> std::io::Result<Self> {
+        use std::io::{ErrorKind, Write};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        static NEXT_FILE: AtomicUsize = AtomicUsize::new(0);
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut last_error = None;
+        for _ in 0..Self::ATTEMPTS {
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.subsec_nanos());
+            let path = std::env::temp_dir().join(format!(
+                "kumou-user-dictionary-{}-{}-{nanos}.csv",
+                std::process::id(),
+                NEXT_FILE.fetch_add(1, Ordering::Relaxed)
+            ));
+            match options.open(&path) {
+                Ok(mut file) => {
+                    let created = Self { path };
+                    file.write_all(contents.as_bytes())?;
+                    return Ok(created);
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => last_error = Some(e),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| ErrorKind::AlreadyExists.into()))
+    }
+}
+
+#[cfg(feature = "tokenizer")]
+impl Drop for TempCsv {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_entries() {
+        let dictionary = UserDictionary::parse(
+            "# surface,POS,reading,base form\n\
+             東京スカイツリー,名詞-固有名詞-一般,トウキョウスカイツリー,東京スカイツリー\n\
+             \n\
+             ググる,動詞-自立,ググル,\n",
+        )
+        .unwrap();
+        assert_eq!(dictionary.entries.len(), 2);
+
+        let tree = &dictionary.entries[0];
+        assert_eq!(tree.pos.major, PosMajor::Noun);
+        assert_eq!(tree.pos.sub2.as_str(), "一般");
+        assert_eq!(tree.pos.sub3.as_str(), "*");
+        assert_eq!(tree.reading, "トウキョウスカイツリー");
+
+        // An empty base form defaults to the surface
+        let guguru = &dictionary.entries[1];
+        assert_eq!(guguru.base_form, "ググる");
+        assert_eq!(guguru.pos.sub1.as_str(), "自立");
+    }
+
+    #[test]
+    fn column_count() {
+        assert!(matches!(
+            UserDictionary::parse("ググる,動詞,ググル"),
+            Err(AnalysisError::UserDictionaryColumns { line: 1, found: 3 })
+        ));
+        assert!(matches!(
+            UserDictionary::parse("# header\nググる,動詞,ググル,ググる,extra"),
+            Err(AnalysisError::UserDictionaryColumns { line: 2, found: 5 })
+        ));
+    }
+
+    #[test]
+    fn empty_surface() {
+        assert!(matches!(
+            UserDictionary::parse(" ,名詞,ナニ,"),
+            Err(AnalysisError::UserDictionaryEmptySurface { line: 1 })
+        ));
+    }
+
+    #[test]
+    fn duplicate_surface() {
+        assert!(matches!(
+            UserDictionary::parse("ググる,動詞,ググル,\nググる,名詞,ググル,"),
+            Err(AnalysisError::UserDictionaryDuplicate { line: 2, surface }) if surface == "ググる"
+        ));
+    }
+
+    #[test]
+    fn unknown_pos() {
+        for pos in ["形容", "名詞-一般-*-*-*", ""] {
+            assert!(
+                matches!(
+                    UserDictionary::parse(&format!("ググる,{pos},ググル,")),
+                    Err(AnalysisError::UserDictionaryPos { line: 1, .. })
+                ),
+                "{pos}"
+            );
+        }
+        // UniDic labels are accepted too
+        assert!(UserDictionary::parse("ググる,接頭辞,ググル,").is_ok());
+    }
+
+    #[test]
+    fn reading_must_be_katakana() {
+        for reading in ["", "ぐぐる", "gugu", "グ具ル"] {
+            assert!(
+                matches!(
+                    UserDictionary::parse(&format!("ググる,動詞,{reading},")),
+                    Err(AnalysisError::UserDictionaryReading { line: 1, .. })
+                ),
+                "{reading}"
+            );
+        }
+        assert!(UserDictionary::parse("ラーメン,名詞,ラーメン,").is_ok());
+    }
+}